@@ -1,6 +1,6 @@
 use node_template_runtime::{
-	AccountId, AuraConfig, BalancesConfig, GrandpaConfig, RuntimeGenesisConfig, Signature,
-	SudoConfig, SystemConfig, WASM_BINARY, ERCModuleConfig
+	AccountId, AuraConfig, BalancesConfig, GovTokenConfig, GrandpaConfig, RuntimeGenesisConfig,
+	Signature, SudoConfig, SystemConfig, UsdTokenConfig, WASM_BINARY,
 };
 use sc_service::ChainType;
 use sp_consensus_aura::sr25519::AuthorityId as AuraId;
@@ -14,6 +14,21 @@ use sp_runtime::traits::{IdentifyAccount, Verify};
 /// Specialized `ChainSpec`. This is a specialization of the general Substrate ChainSpec type.
 pub type ChainSpec = sc_service::GenericChainSpec<RuntimeGenesisConfig>;
 
+/// Name, symbol and decimals of the token hosted by the `UsdToken` instance.
+const USD_TOKEN_METADATA: (&str, &str, u64) = ("USD Token", "USD", 6);
+/// Name, symbol and decimals of the token hosted by the `GovToken` instance.
+const GOV_TOKEN_METADATA: (&str, &str, u64) = ("Governance Token", "GOV", 18);
+
+/// Chain properties advertising the ERC20 tokens, in instance order.
+fn token_properties() -> sc_service::Properties {
+	let mut properties = sc_service::Properties::new();
+	properties
+		.insert("tokenSymbol".into(), vec![USD_TOKEN_METADATA.1, GOV_TOKEN_METADATA.1].into());
+	properties
+		.insert("tokenDecimals".into(), vec![USD_TOKEN_METADATA.2, GOV_TOKEN_METADATA.2].into());
+	properties
+}
+
 /// Generate a crypto pair from seed.
 pub fn get_from_seed<TPublic: Public>(seed: &str) -> <TPublic::Pair as Pair>::Public {
 	TPublic::Pair::from_string(&format!("//{}", seed), None)
@@ -70,7 +85,7 @@ pub fn development_config() -> Result<ChainSpec, String> {
 		None,
 		None,
 		// Properties
-		None,
+		Some(token_properties()),
 		// Extensions
 		None,
 	))
@@ -116,9 +131,9 @@ pub fn local_testnet_config() -> Result<ChainSpec, String> {
 		None,
 		// Protocol ID
 		None,
-		// Properties
-		None,
 		None,
+		// Properties
+		Some(token_properties()),
 		// Extensions
 		None,
 	))
@@ -151,11 +166,28 @@ fn testnet_genesis(
 		},
 		sudo: SudoConfig {
 			// Assign network admin rights.
-			key: Some(root_key),
+			key: Some(root_key.clone()),
 		},
 		transaction_payment: Default::default(),
-		erc_module: ERCModuleConfig {
-			minters: endowed_accounts,
+		usd_token: UsdTokenConfig {
+			admins: vec![root_key.clone()],
+			minters: endowed_accounts.clone(),
+			burners: endowed_accounts.clone(),
+			pausers: vec![root_key.clone()],
+			name: USD_TOKEN_METADATA.0.as_bytes().to_vec(),
+			symbol: USD_TOKEN_METADATA.1.as_bytes().to_vec(),
+			decimals: Some(USD_TOKEN_METADATA.2),
+			..Default::default()
+		},
+		gov_token: GovTokenConfig {
+			admins: vec![root_key.clone()],
+			minters: endowed_accounts.clone(),
+			burners: endowed_accounts,
+			pausers: vec![root_key],
+			name: GOV_TOKEN_METADATA.0.as_bytes().to_vec(),
+			symbol: GOV_TOKEN_METADATA.1.as_bytes().to_vec(),
+			decimals: Some(GOV_TOKEN_METADATA.2),
+			..Default::default()
 		},
 	}
 }
@@ -5,86 +5,613 @@ use super::*;
 
 #[allow(unused)]
 use crate::Pallet as ERC20;
+use codec::Encode;
 use frame_benchmarking::v2::*;
-use frame_system::RawOrigin;
+use frame_support::BoundedVec;
+use frame_system::{pallet_prelude::BlockNumberFor, RawOrigin};
 
 const SEED: u32 = 0;
 
-#[benchmarks]
+/// Gives `who` enough native currency to reserve allowance deposits.
+fn fund_deposit<T: Config<I>, I: 'static>(who: &T::AccountId) {
+	let amount = T::Currency::minimum_balance().saturating_add(T::AllowanceDeposit::get());
+	T::Currency::make_free_balance_be(who, amount.saturating_add(amount));
+}
+
+/// Charges the highest transfer fee, credited to an account holding the minimum balance.
+fn set_max_transfer_fee<T: Config<I>, I: 'static>() {
+	<TransferFeeBps<T, I>>::put(T::MaxFeeBps::get());
+	<Balances<T, I>>::insert(T::TransferFeeAccount::get(), T::MinimumBalance::get());
+}
+
+#[instance_benchmarks]
 mod benchmarks {
 	use super::*;
 
 	#[benchmark]
 	fn transfer() {
-		let value = 100u64.into();
+		let value: T::Balance = 200u32.into();
+		let initial: T::Balance = 1000u32.into();
 		let caller: T::AccountId = whitelisted_caller();
 
 		let recipient: T::AccountId = account("Bob", 0, SEED);
 		let recipient_lookup = T::Lookup::unlookup(recipient.clone());
 
-		<Balances<T>>::insert(caller.clone(), 1000u64);
+		<Balances<T, I>>::insert(caller.clone(), initial);
+		set_max_transfer_fee::<T, I>();
+		let fee = ERC20::<T, I>::transfer_fee(&caller, &recipient, value);
 		#[extrinsic_call]
 		transfer(RawOrigin::Signed(caller.clone()), recipient_lookup, value);
 
-		assert_eq!(<Balances<T>>::get(caller.clone()), 900u64);
-		assert_eq!(<Balances<T>>::get(recipient.clone()), 100u64);
+		assert_eq!(<Balances<T, I>>::get(caller.clone()), initial - value);
+		assert_eq!(<Balances<T, I>>::get(recipient.clone()), value - fee);
+	}
+
+	#[benchmark]
+	fn transfer_keep_alive() {
+		let value: T::Balance = 200u32.into();
+		let initial: T::Balance = 1000u32.into();
+		let caller: T::AccountId = whitelisted_caller();
+
+		let recipient: T::AccountId = account("Bob", 0, SEED);
+		let recipient_lookup = T::Lookup::unlookup(recipient.clone());
+
+		<Balances<T, I>>::insert(caller.clone(), initial);
+		set_max_transfer_fee::<T, I>();
+		let fee = ERC20::<T, I>::transfer_fee(&caller, &recipient, value);
+		#[extrinsic_call]
+		transfer_keep_alive(RawOrigin::Signed(caller.clone()), recipient_lookup, value);
+
+		assert_eq!(<Balances<T, I>>::get(caller.clone()), initial - value);
+		assert_eq!(<Balances<T, I>>::get(recipient.clone()), value - fee);
 	}
 
 	#[benchmark]
 	fn transfer_from() {
-		let value = 100u64.into();
+		let value: T::Balance = 200u32.into();
+		let initial: T::Balance = 1000u32.into();
 		let caller: T::AccountId = whitelisted_caller(); //отправляет транзу
 		let recipient: T::AccountId = account("Bob", 0, SEED); //получает
 		let recipient_lookup = T::Lookup::unlookup(recipient.clone());
 		let owner: T::AccountId = account("Alice", 0, SEED);
 		let owner_lookup = T::Lookup::unlookup(owner.clone());
 
-		<Balances<T>>::insert(owner.clone(), 1000u64);
-		<Allowances<T>>::insert(owner.clone(), caller.clone(), 1000u64);
+		<Balances<T, I>>::insert(owner.clone(), initial);
+		<Allowances<T, I>>::insert(owner.clone(), caller.clone(), initial);
+		set_max_transfer_fee::<T, I>();
+		let fee = ERC20::<T, I>::transfer_fee(&owner, &recipient, value);
 		#[extrinsic_call]
 		transfer_from(RawOrigin::Signed(caller.clone()), owner_lookup, recipient_lookup, value);
 
-		assert_eq!(<Balances<T>>::get(owner.clone()), 900u64);
-		assert_eq!(<Balances<T>>::get(recipient.clone()), 100u64);
-		assert_eq!(<Allowances<T>>::get(owner.clone(), caller.clone()), 900u64);
+		assert_eq!(<Balances<T, I>>::get(owner.clone()), initial - value);
+		assert_eq!(<Balances<T, I>>::get(recipient.clone()), value - fee);
+		assert_eq!(<Allowances<T, I>>::get(owner.clone(), caller.clone()), initial - value);
 	}
 
 	#[benchmark]
 	fn approve() {
-		let value = 100u64.into();
+		let value: T::Balance = 100u32.into();
 		let caller: T::AccountId = whitelisted_caller();
 		let spender: T::AccountId = account("Bob", 0, SEED);
 		let spender_lookup = T::Lookup::unlookup(spender.clone());
 
+		fund_deposit::<T, I>(&caller);
 		#[extrinsic_call]
 		approve(RawOrigin::Signed(caller.clone()), spender_lookup.clone(), value);
 
-		assert_eq!(<Allowances<T>>::get(caller.clone(), spender.clone()), value);
+		assert_eq!(<Allowances<T, I>>::get(caller.clone(), spender.clone()), value);
+		assert_eq!(
+			<AllowanceDeposits<T, I>>::get(caller.clone(), spender),
+			Some((caller, T::AllowanceDeposit::get()))
+		);
+	}
+
+	#[benchmark]
+	fn increase_allowance() {
+		let value: T::Balance = 100u32.into();
+		let caller: T::AccountId = whitelisted_caller();
+		let spender: T::AccountId = account("Bob", 0, SEED);
+		let spender_lookup = T::Lookup::unlookup(spender.clone());
+
+		// The worst case creates the allowance entry, reserving its deposit.
+		fund_deposit::<T, I>(&caller);
+		#[extrinsic_call]
+		_(RawOrigin::Signed(caller.clone()), spender_lookup, value);
+
+		assert_eq!(<Allowances<T, I>>::get(caller.clone(), spender.clone()), value);
+		assert_eq!(
+			<AllowanceDeposits<T, I>>::get(caller.clone(), spender),
+			Some((caller, T::AllowanceDeposit::get()))
+		);
+	}
+
+	#[benchmark]
+	fn decrease_allowance() {
+		let value: T::Balance = 100u32.into();
+		let initial: T::Balance = 1000u32.into();
+		let caller: T::AccountId = whitelisted_caller();
+		let spender: T::AccountId = account("Bob", 0, SEED);
+		let spender_lookup = T::Lookup::unlookup(spender.clone());
+
+		<Allowances<T, I>>::insert(caller.clone(), spender.clone(), initial);
+		#[extrinsic_call]
+		_(RawOrigin::Signed(caller.clone()), spender_lookup, value);
+
+		assert_eq!(<Allowances<T, I>>::get(caller, spender), initial - value);
+	}
+
+	#[benchmark]
+	fn remove_allowance() -> Result<(), BenchmarkError> {
+		let initial: T::Balance = 1000u32.into();
+		let caller: T::AccountId = whitelisted_caller();
+		let spender: T::AccountId = account("Bob", 0, SEED);
+		let spender_lookup = T::Lookup::unlookup(spender.clone());
+
+		fund_deposit::<T, I>(&caller);
+		ERC20::<T, I>::_approve(caller.clone(), spender.clone(), initial)?;
+		#[extrinsic_call]
+		_(RawOrigin::Signed(caller.clone()), spender_lookup);
+
+		assert!(!<Allowances<T, I>>::contains_key(&caller, &spender));
+		assert!(!<AllowanceDeposits<T, I>>::contains_key(caller, spender));
+		Ok(())
 	}
 
 	#[benchmark]
 	fn mint() {
-		let value = 100u64.into();
+		let value: T::Balance = 100u32.into();
 		let caller: T::AccountId = whitelisted_caller();
 
-		<Minters<T>>::insert(caller.clone(), ());
+		<Roles<T, I>>::insert(Role::Minter, caller.clone(), ());
 		#[extrinsic_call]
 		mint(RawOrigin::Signed(caller.clone()), value);
 
-		assert_eq!(<Balances<T>>::get(caller.clone()), value);
+		assert_eq!(<Balances<T, I>>::get(caller.clone()), value);
 	}
 
 	#[benchmark]
 	fn burn() {
-		let value = 100u64.into();
+		let value: T::Balance = 100u32.into();
+		let initial: T::Balance = 1000u32.into();
 		let caller: T::AccountId = whitelisted_caller();
 
-		<Balances<T>>::insert(caller.clone(), 1000u64);
+		<Roles<T, I>>::insert(Role::Burner, caller.clone(), ());
+		<Balances<T, I>>::insert(caller.clone(), initial);
+		<TotalSupply<T, I>>::put(initial);
 
 		#[extrinsic_call]
 		burn(RawOrigin::Signed(caller.clone()), value);
 
-		assert_eq!(<Balances<T>>::get(caller.clone()), 900u64);
+		assert_eq!(<Balances<T, I>>::get(caller.clone()), initial - value);
+	}
+
+	#[benchmark]
+	fn burn_from() {
+		let value: T::Balance = 100u32.into();
+		let initial: T::Balance = 1000u32.into();
+		let caller: T::AccountId = whitelisted_caller();
+		let owner: T::AccountId = account("Alice", 0, SEED);
+		let owner_lookup = T::Lookup::unlookup(owner.clone());
+
+		<Roles<T, I>>::insert(Role::Burner, caller.clone(), ());
+		<Balances<T, I>>::insert(owner.clone(), initial);
+		<Allowances<T, I>>::insert(owner.clone(), caller.clone(), initial);
+		<TotalSupply<T, I>>::put(initial);
+
+		#[extrinsic_call]
+		_(RawOrigin::Signed(caller.clone()), owner_lookup, value);
+
+		assert_eq!(<Balances<T, I>>::get(owner.clone()), initial - value);
+		assert_eq!(<Allowances<T, I>>::get(owner, caller), initial - value);
+		assert_eq!(<TotalSupply<T, I>>::get(), initial - value);
+	}
+
+	#[benchmark]
+	fn permit() {
+		let value: T::Balance = 100u32.into();
+		let caller: T::AccountId = whitelisted_caller();
+		let (signer, owner) = T::BenchmarkHelper::signer();
+		let owner_lookup = T::Lookup::unlookup(owner.clone());
+		let spender: T::AccountId = account("Bob", 0, SEED);
+		let spender_lookup = T::Lookup::unlookup(spender.clone());
+		let deadline = frame_system::Pallet::<T>::block_number();
+		let payload =
+			ERC20::<T, I>::permit_payload(owner.clone(), spender.clone(), value, deadline);
+		let signature = T::BenchmarkHelper::sign(&signer, &payload.encode());
+
+		fund_deposit::<T, I>(&caller);
+		#[extrinsic_call]
+		_(
+			RawOrigin::Signed(caller.clone()),
+			owner_lookup,
+			spender_lookup,
+			value,
+			deadline,
+			signature,
+		);
+
+		assert_eq!(<Allowances<T, I>>::get(owner.clone(), spender.clone()), value);
+		assert_eq!(
+			<AllowanceDeposits<T, I>>::get(owner.clone(), spender),
+			Some((caller, T::AllowanceDeposit::get()))
+		);
+		assert_eq!(<PermitNonces<T, I>>::get(owner), 1);
+	}
+
+	#[benchmark]
+	fn batch_transfer(n: Linear<1, { T::MaxBatchSize::get() }>) {
+		let value: T::Balance = 100u32.into();
+		let caller: T::AccountId = whitelisted_caller();
+		let recipients: Vec<T::AccountId> = (0..n).map(|i| account("recipient", i, SEED)).collect();
+		let transfers: Vec<_> =
+			recipients.iter().map(|r| (T::Lookup::unlookup(r.clone()), value)).collect();
+
+		<Balances<T, I>>::insert(caller.clone(), value * n.into());
+		set_max_transfer_fee::<T, I>();
+		let fee = ERC20::<T, I>::transfer_fee(&caller, &recipients[0], value);
+		#[extrinsic_call]
+		_(RawOrigin::Signed(caller.clone()), transfers);
+
+		assert_eq!(<Balances<T, I>>::get(caller), T::Balance::zero());
+		for recipient in recipients {
+			assert_eq!(<Balances<T, I>>::get(recipient), value - fee);
+		}
+	}
+
+	#[benchmark]
+	fn set_metadata() -> Result<(), BenchmarkError> {
+		let origin =
+			T::AdminOrigin::try_successful_origin().map_err(|_| BenchmarkError::Weightless)?;
+		let name: Vec<u8> = (0..T::StringLimit::get()).map(|_| b'N').collect();
+		let symbol: Vec<u8> = (0..T::StringLimit::get()).map(|_| b'S').collect();
+
+		#[extrinsic_call]
+		_(origin as T::RuntimeOrigin, name.clone(), symbol.clone(), 12);
+
+		assert_eq!(<Name<T, I>>::get().into_inner(), name);
+		assert_eq!(<Symbol<T, I>>::get().into_inner(), symbol);
+		assert_eq!(<Decimals<T, I>>::get(), 12);
+		Ok(())
+	}
+
+	#[benchmark]
+	fn set_fee_rate() -> Result<(), BenchmarkError> {
+		let origin =
+			T::AdminOrigin::try_successful_origin().map_err(|_| BenchmarkError::Weightless)?;
+		let rate = FixedU128::from_u32(2);
+
+		#[extrinsic_call]
+		_(origin as T::RuntimeOrigin, rate);
+
+		assert_eq!(<FeeRate<T, I>>::get(), rate);
+		Ok(())
+	}
+
+	#[benchmark]
+	fn grant_role() {
+		let caller: T::AccountId = whitelisted_caller();
+		let account: T::AccountId = account("Bob", 0, SEED);
+		let account_lookup = T::Lookup::unlookup(account.clone());
+
+		<Roles<T, I>>::insert(Role::Admin, caller.clone(), ());
+		#[extrinsic_call]
+		_(RawOrigin::Signed(caller.clone()), Role::Minter, account_lookup);
+
+		assert!(ERC20::<T, I>::has_role(Role::Minter, &account));
+	}
+
+	#[benchmark]
+	fn revoke_role() {
+		let caller: T::AccountId = whitelisted_caller();
+		let account: T::AccountId = account("Bob", 0, SEED);
+		let account_lookup = T::Lookup::unlookup(account.clone());
+
+		<Roles<T, I>>::insert(Role::Admin, caller.clone(), ());
+		<Roles<T, I>>::insert(Role::Minter, account.clone(), ());
+		<RoleMemberCount<T, I>>::insert(Role::Minter, 1u32);
+		#[extrinsic_call]
+		_(RawOrigin::Signed(caller.clone()), Role::Minter, account_lookup);
+
+		assert!(!ERC20::<T, I>::has_role(Role::Minter, &account));
+	}
+
+	#[benchmark]
+	fn set_role_admin() -> Result<(), BenchmarkError> {
+		let origin =
+			T::RoleAdminOrigin::try_successful_origin().map_err(|_| BenchmarkError::Weightless)?;
+
+		#[extrinsic_call]
+		_(origin as T::RuntimeOrigin, Role::Minter, Role::Pauser);
+
+		assert_eq!(ERC20::<T, I>::role_admin(Role::Minter), Role::Pauser);
+		Ok(())
+	}
+
+	#[benchmark]
+	fn pause() {
+		let caller: T::AccountId = whitelisted_caller();
+
+		<Roles<T, I>>::insert(Role::Pauser, caller.clone(), ());
+		#[extrinsic_call]
+		_(RawOrigin::Signed(caller), Some(Operation::Transfer));
+
+		assert!(ERC20::<T, I>::is_paused(Operation::Transfer));
+	}
+
+	#[benchmark]
+	fn unpause() {
+		let caller: T::AccountId = whitelisted_caller();
+
+		<Roles<T, I>>::insert(Role::Pauser, caller.clone(), ());
+		<PausedOperations<T, I>>::insert(Operation::Transfer, ());
+		#[extrinsic_call]
+		_(RawOrigin::Signed(caller), Some(Operation::Transfer));
+
+		assert!(!ERC20::<T, I>::is_paused(Operation::Transfer));
+	}
+
+	#[benchmark]
+	fn set_mint_quota() {
+		let allowance: T::Balance = 1000u32.into();
+		let max_per_period: T::Balance = 100u32.into();
+		let period: BlockNumberFor<T> = 10u32.into();
+		let caller: T::AccountId = whitelisted_caller();
+		let minter: T::AccountId = account("Bob", 0, SEED);
+		let minter_lookup = T::Lookup::unlookup(minter.clone());
+
+		<Roles<T, I>>::insert(Role::Admin, caller.clone(), ());
+		#[extrinsic_call]
+		_(RawOrigin::Signed(caller), minter_lookup, allowance, Some((max_per_period, period)));
+
+		assert_eq!(
+			ERC20::<T, I>::mint_quota(&minter).map(|quota| quota.remaining),
+			Some(allowance)
+		);
+	}
+
+	#[benchmark]
+	fn clear_mint_quota() {
+		let caller: T::AccountId = whitelisted_caller();
+		let minter: T::AccountId = account("Bob", 0, SEED);
+		let minter_lookup = T::Lookup::unlookup(minter.clone());
+
+		<Roles<T, I>>::insert(Role::Admin, caller.clone(), ());
+		<MintQuotas<T, I>>::insert(
+			minter.clone(),
+			MintQuota { remaining: T::Balance::zero(), rate_limit: None },
+		);
+		#[extrinsic_call]
+		_(RawOrigin::Signed(caller), minter_lookup);
+
+		assert!(ERC20::<T, I>::mint_quota(&minter).is_none());
+	}
+
+	#[benchmark]
+	fn vested_transfer() {
+		let initial: T::Balance = 1000u32.into();
+		let caller: T::AccountId = whitelisted_caller();
+		let target: T::AccountId = account("Bob", 0, SEED);
+		let target_lookup = T::Lookup::unlookup(target.clone());
+		let schedule = VestingSchedule {
+			locked: 100u32.into(),
+			per_block: 1u32.into(),
+			start: 10u32.into(),
+			cliff: 5u32.into(),
+		};
+		let schedules: Vec<_> = (1..T::MaxVestingSchedules::get()).map(|_| schedule).collect();
+
+		<Roles<T, I>>::insert(Role::Minter, caller.clone(), ());
+		<Balances<T, I>>::insert(caller.clone(), initial);
+		<Vesting<T, I>>::insert(target.clone(), BoundedVec::truncate_from(schedules));
+		#[extrinsic_call]
+		_(RawOrigin::Signed(caller), target_lookup, schedule);
+
+		assert_eq!(<Vesting<T, I>>::get(&target).len() as u32, T::MaxVestingSchedules::get());
+		assert_eq!(<Balances<T, I>>::get(target), schedule.locked);
+	}
+
+	#[benchmark]
+	fn vest() {
+		let caller: T::AccountId = whitelisted_caller();
+		let schedule = VestingSchedule {
+			locked: 100u32.into(),
+			per_block: 1u32.into(),
+			start: Zero::zero(),
+			cliff: Zero::zero(),
+		};
+		let schedules: Vec<_> = (0..T::MaxVestingSchedules::get()).map(|_| schedule).collect();
+
+		<Vesting<T, I>>::insert(caller.clone(), BoundedVec::truncate_from(schedules));
+		frame_system::Pallet::<T>::set_block_number(10u32.into());
+		#[extrinsic_call]
+		_(RawOrigin::Signed(caller.clone()));
+
+		let unvested: T::Balance = 90u32.into();
+		assert_eq!(<Locked<T, I>>::get(caller), unvested * T::MaxVestingSchedules::get().into());
+	}
+
+	#[benchmark]
+	fn snapshot() -> Result<(), BenchmarkError> {
+		let origin =
+			T::AdminOrigin::try_successful_origin().map_err(|_| BenchmarkError::Weightless)?;
+
+		#[extrinsic_call]
+		_(origin as T::RuntimeOrigin);
+
+		assert_eq!(<CurrentSnapshotId<T, I>>::get(), 1);
+		Ok(())
+	}
+
+	#[benchmark]
+	fn delegate() {
+		let balance: T::Balance = 1000u32.into();
+		let caller: T::AccountId = whitelisted_caller();
+		let previous: T::AccountId = account("Alice", 0, SEED);
+		let delegatee: T::AccountId = account("Bob", 0, SEED);
+		let delegatee_lookup = T::Lookup::unlookup(delegatee.clone());
+
+		<Balances<T, I>>::insert(caller.clone(), balance);
+		<Balances<T, I>>::insert(delegatee.clone(), balance);
+		ERC20::<T, I>::_delegate(caller.clone(), previous.clone());
+		ERC20::<T, I>::_delegate(delegatee.clone(), delegatee.clone());
+		frame_system::Pallet::<T>::set_block_number(10u32.into());
+		#[extrinsic_call]
+		_(RawOrigin::Signed(caller.clone()), delegatee_lookup);
+
+		assert_eq!(<Delegates<T, I>>::get(caller), Some(delegatee.clone()));
+		assert_eq!(ERC20::<T, I>::get_votes(&delegatee), balance + balance);
+		assert_eq!(ERC20::<T, I>::get_votes(&previous), T::Balance::zero());
+	}
+
+	#[benchmark]
+	fn flash_mint() {
+		// The fee is only charged once the receiver holds the minimum balance.
+		<Balances<T, I>>::insert(T::FlashMintFeeReceiver::get(), T::MinimumBalance::get());
+		let max_fee = ERC20::<T, I>::flash_mint_fee(ERC20::<T, I>::max_flash_mint());
+		let initial = max_fee.saturating_add(T::MinimumBalance::get());
+		let caller: T::AccountId = whitelisted_caller();
+		let call: <T as Config<I>>::RuntimeCall =
+			frame_system::Call::<T>::remark { remark: Vec::new() }.into();
+
+		ERC20::<T, I>::_mint(caller.clone(), initial).unwrap();
+		let amount = ERC20::<T, I>::max_flash_mint();
+		let fee = ERC20::<T, I>::flash_mint_fee(amount);
+		#[extrinsic_call]
+		_(RawOrigin::Signed(caller.clone()), amount, Box::new(call));
+
+		assert_eq!(<Balances<T, I>>::get(caller), initial - fee);
+	}
+
+	#[benchmark]
+	fn freeze_account() -> Result<(), BenchmarkError> {
+		let origin =
+			T::FreezeOrigin::try_successful_origin().map_err(|_| BenchmarkError::Weightless)?;
+		let account: T::AccountId = account("Alice", 0, SEED);
+		let account_lookup = T::Lookup::unlookup(account.clone());
+
+		#[extrinsic_call]
+		_(origin as T::RuntimeOrigin, account_lookup);
+
+		assert!(ERC20::<T, I>::is_frozen(&account));
+		Ok(())
+	}
+
+	#[benchmark]
+	fn thaw_account() -> Result<(), BenchmarkError> {
+		let origin =
+			T::FreezeOrigin::try_successful_origin().map_err(|_| BenchmarkError::Weightless)?;
+		let account: T::AccountId = account("Alice", 0, SEED);
+		let account_lookup = T::Lookup::unlookup(account.clone());
+
+		<Frozen<T, I>>::insert(account.clone(), ());
+		#[extrinsic_call]
+		_(origin as T::RuntimeOrigin, account_lookup);
+
+		assert!(!ERC20::<T, I>::is_frozen(&account));
+		Ok(())
+	}
+
+	#[benchmark]
+	fn add_to_allowlist() -> Result<(), BenchmarkError> {
+		let origin =
+			T::AdminOrigin::try_successful_origin().map_err(|_| BenchmarkError::Weightless)?;
+		let account: T::AccountId = account("Alice", 0, SEED);
+		let account_lookup = T::Lookup::unlookup(account.clone());
+
+		#[extrinsic_call]
+		_(origin as T::RuntimeOrigin, account_lookup);
+
+		assert!(ERC20::<T, I>::is_allowlisted(&account));
+		Ok(())
+	}
+
+	#[benchmark]
+	fn remove_from_allowlist() -> Result<(), BenchmarkError> {
+		let origin =
+			T::AdminOrigin::try_successful_origin().map_err(|_| BenchmarkError::Weightless)?;
+		let account: T::AccountId = account("Alice", 0, SEED);
+		let account_lookup = T::Lookup::unlookup(account.clone());
+
+		<Allowlist<T, I>>::insert(account.clone(), ());
+		#[extrinsic_call]
+		_(origin as T::RuntimeOrigin, account_lookup);
+
+		assert!(!ERC20::<T, I>::is_allowlisted(&account));
+		Ok(())
+	}
+
+	#[benchmark]
+	fn batch_add_to_allowlist(
+		n: Linear<1, { T::MaxBatchSize::get() }>,
+	) -> Result<(), BenchmarkError> {
+		let origin =
+			T::AdminOrigin::try_successful_origin().map_err(|_| BenchmarkError::Weightless)?;
+		let accounts: Vec<T::AccountId> = (0..n).map(|i| account("member", i, SEED)).collect();
+		let lookups: Vec<_> = accounts.iter().cloned().map(T::Lookup::unlookup).collect();
+
+		#[extrinsic_call]
+		_(origin as T::RuntimeOrigin, lookups);
+
+		assert!(accounts.iter().all(ERC20::<T, I>::is_allowlisted));
+		Ok(())
+	}
+
+	#[benchmark]
+	fn batch_remove_from_allowlist(
+		n: Linear<1, { T::MaxBatchSize::get() }>,
+	) -> Result<(), BenchmarkError> {
+		let origin =
+			T::AdminOrigin::try_successful_origin().map_err(|_| BenchmarkError::Weightless)?;
+		let accounts: Vec<T::AccountId> = (0..n).map(|i| account("member", i, SEED)).collect();
+		let lookups: Vec<_> = accounts.iter().cloned().map(T::Lookup::unlookup).collect();
+
+		for account in &accounts {
+			<Allowlist<T, I>>::insert(account, ());
+		}
+		#[extrinsic_call]
+		_(origin as T::RuntimeOrigin, lookups);
+
+		assert!(!accounts.iter().any(ERC20::<T, I>::is_allowlisted));
+		Ok(())
+	}
+
+	#[benchmark]
+	fn set_allowlist_enabled() -> Result<(), BenchmarkError> {
+		let origin =
+			T::AdminOrigin::try_successful_origin().map_err(|_| BenchmarkError::Weightless)?;
+
+		#[extrinsic_call]
+		_(origin as T::RuntimeOrigin, true);
+
+		assert!(<AllowlistEnabled<T, I>>::get());
+		Ok(())
+	}
+
+	#[benchmark]
+	fn set_transfer_fee() -> Result<(), BenchmarkError> {
+		let origin =
+			T::AdminOrigin::try_successful_origin().map_err(|_| BenchmarkError::Weightless)?;
+		let bps = T::MaxFeeBps::get();
+
+		#[extrinsic_call]
+		_(origin as T::RuntimeOrigin, bps);
+
+		assert_eq!(<TransferFeeBps<T, I>>::get(), bps);
+		Ok(())
+	}
+
+	#[benchmark]
+	fn set_transfer_fee_exempt() -> Result<(), BenchmarkError> {
+		let origin =
+			T::AdminOrigin::try_successful_origin().map_err(|_| BenchmarkError::Weightless)?;
+		let account: T::AccountId = account("Alice", 0, SEED);
+		let account_lookup = T::Lookup::unlookup(account.clone());
+
+		#[extrinsic_call]
+		_(origin as T::RuntimeOrigin, account_lookup, true);
+
+		assert!(<TransferFeeExempt<T, I>>::contains_key(account));
+		Ok(())
 	}
 
 	impl_benchmark_test_suite!(ERC20, crate::mock::new_test_ext(), crate::mock::Test);
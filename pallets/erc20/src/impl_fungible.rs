@@ -0,0 +1,238 @@
+//! Implementation of the `fungible` traits, so that other pallets can use the token.
+//!
+//! Minting, burning and transfers through `fungible::Mutate` go through `_mint`, `_burn` and
+//! `_transfer`, so the supply cap, the pause switches and the events apply as for the calls of
+//! the pallet, frozen accounts included. Balances are not written through `fungible::Unbalanced`
+//! while the token is paused or for frozen accounts, nor credited to accounts off the allowlist.
+//!
+//! Issuance through `fungible::Balanced` is not capped: `issue`, and a debt of `deposit` dropped
+//! instead of offset, raise the total supply past `Config::MaxSupply` and while `Operation::Mint`
+//! is paused. `deposit` cannot tell new tokens from the refund of a credit, as `TokenFeeAdapter`
+//! makes, so other pallets minting tokens must use `fungible::Mutate`.
+//!
+//! Holds are kept in `Holds` by `Config::RuntimeHoldReason` and count in the reserved balance, so
+//! `reserve` and `unreserve` leave them alone.
+
+use super::*;
+use frame_support::{
+	ensure,
+	traits::tokens::{
+		fungible, DepositConsequence, Fortitude, Precision, Preservation, Provenance,
+		WithdrawConsequence,
+	},
+};
+
+impl<T: Config<I>, I: 'static> fungible::Inspect<T::AccountId> for Pallet<T, I> {
+	type Balance = T::Balance;
+
+	fn total_issuance() -> Self::Balance {
+		TotalSupply::<T, I>::get()
+	}
+
+	fn minimum_balance() -> Self::Balance {
+		T::MinimumBalance::get()
+	}
+
+	fn total_balance(who: &T::AccountId) -> Self::Balance {
+		Self::total_balance_of(who)
+	}
+
+	fn balance(who: &T::AccountId) -> Self::Balance {
+		Balances::<T, I>::get(who)
+	}
+
+	fn reducible_balance(
+		who: &T::AccountId,
+		preservation: Preservation,
+		_force: Fortitude,
+	) -> Self::Balance {
+		if Self::is_paused(Operation::Transfer) || Self::is_frozen(who) {
+			return Zero::zero()
+		}
+		let balance = Balances::<T, I>::get(who);
+		let locked = Locked::<T, I>::get(who);
+		match preservation {
+			Preservation::Expendable => balance.saturating_sub(locked),
+			Preservation::Protect | Preservation::Preserve => {
+				// The reserved balance keeps the account alive on its own.
+				let minimum = T::MinimumBalance::get().saturating_sub(Reserved::<T, I>::get(who));
+				balance.saturating_sub(locked.max(minimum))
+			},
+		}
+	}
+
+	fn can_deposit(
+		who: &T::AccountId,
+		amount: Self::Balance,
+		provenance: Provenance,
+	) -> DepositConsequence {
+		if amount.is_zero() {
+			return DepositConsequence::Success
+		}
+		let operation = match provenance {
+			Provenance::Minted => Operation::Mint,
+			Provenance::Extant => Operation::Transfer,
+		};
+		if Self::is_paused(operation) ||
+			Self::is_frozen(who) ||
+			Self::ensure_allowlisted(who).is_err()
+		{
+			return DepositConsequence::Blocked
+		}
+		if provenance == Provenance::Minted && amount > Self::remaining_mintable() {
+			return DepositConsequence::Overflow
+		}
+		match Balances::<T, I>::get(who).checked_add(&amount) {
+			Some(balance) if Self::below_minimum(who, balance) => DepositConsequence::BelowMinimum,
+			Some(_) => DepositConsequence::Success,
+			None => DepositConsequence::Overflow,
+		}
+	}
+
+	fn can_withdraw(
+		who: &T::AccountId,
+		amount: Self::Balance,
+	) -> WithdrawConsequence<Self::Balance> {
+		if amount.is_zero() {
+			return WithdrawConsequence::Success
+		}
+		if Self::is_paused(Operation::Transfer) || Self::is_frozen(who) {
+			return WithdrawConsequence::Frozen
+		}
+		if TotalSupply::<T, I>::get().checked_sub(&amount).is_none() {
+			return WithdrawConsequence::Underflow
+		}
+		match Balances::<T, I>::get(who).checked_sub(&amount) {
+			Some(balance) if balance < Locked::<T, I>::get(who) => WithdrawConsequence::Frozen,
+			Some(balance) if !balance.is_zero() && Self::below_minimum(who, balance) =>
+				WithdrawConsequence::ReducedToZero(balance),
+			Some(_) => WithdrawConsequence::Success,
+			None => WithdrawConsequence::BalanceLow,
+		}
+	}
+}
+
+impl<T: Config<I>, I: 'static> fungible::Unbalanced<T::AccountId> for Pallet<T, I> {
+	fn handle_dust(dust: fungible::Dust<T::AccountId, Self>) {
+		// Dropping the credit burns the dust, as `_transfer` and `_burn` do.
+		let _ = dust.into_credit();
+	}
+
+	fn write_balance(
+		who: &T::AccountId,
+		amount: Self::Balance,
+	) -> Result<Option<Self::Balance>, DispatchError> {
+		ensure!(!Paused::<T, I>::get(), Error::<T, I>::TokenPaused);
+		Self::ensure_not_frozen(who)?;
+		if amount > Balances::<T, I>::get(who) {
+			Self::ensure_allowlisted(who)?;
+		}
+		let dust = Self::write_or_reap(who, amount);
+		if dust.is_zero() {
+			return Ok(None)
+		}
+		Self::deposit_event(Event::<T, I>::DustLost { account: who.clone(), amount: dust });
+		Ok(Some(dust))
+	}
+
+	fn set_total_issuance(amount: Self::Balance) {
+		Self::write_total_supply(amount);
+	}
+}
+
+impl<T: Config<I>, I: 'static> fungible::Mutate<T::AccountId> for Pallet<T, I> {
+	fn mint_into(
+		who: &T::AccountId,
+		amount: Self::Balance,
+	) -> Result<Self::Balance, DispatchError> {
+		Self::_mint(who.clone(), amount)?;
+		Ok(amount)
+	}
+
+	fn burn_from(
+		who: &T::AccountId,
+		amount: Self::Balance,
+		precision: Precision,
+		force: Fortitude,
+	) -> Result<Self::Balance, DispatchError> {
+		let amount = match precision {
+			Precision::BestEffort => amount.min(<Self as fungible::Inspect<_>>::reducible_balance(
+				who,
+				Preservation::Expendable,
+				force,
+			)),
+			Precision::Exact => amount,
+		};
+		Self::_burn(who.clone(), amount)?;
+		Ok(amount)
+	}
+
+	fn transfer(
+		source: &T::AccountId,
+		dest: &T::AccountId,
+		amount: Self::Balance,
+		preservation: Preservation,
+	) -> Result<Self::Balance, DispatchError> {
+		Self::_transfer(
+			source.clone(),
+			dest.clone(),
+			amount,
+			preservation != Preservation::Expendable,
+		)?;
+		Ok(amount)
+	}
+}
+
+impl<T: Config<I>, I: 'static> fungible::Balanced<T::AccountId> for Pallet<T, I> {
+	type OnDropCredit = fungible::DecreaseIssuance<T::AccountId, Self>;
+	type OnDropDebt = fungible::IncreaseIssuance<T::AccountId, Self>;
+}
+
+impl<T: Config<I>, I: 'static> fungible::InspectHold<T::AccountId> for Pallet<T, I> {
+	type Reason = T::RuntimeHoldReason;
+
+	fn total_balance_on_hold(who: &T::AccountId) -> Self::Balance {
+		Self::total_on_hold(who)
+	}
+
+	fn reducible_total_balance_on_hold(who: &T::AccountId, _force: Fortitude) -> Self::Balance {
+		if Paused::<T, I>::get() {
+			return Zero::zero()
+		}
+		Self::total_on_hold(who)
+	}
+
+	fn balance_on_hold(reason: &Self::Reason, who: &T::AccountId) -> Self::Balance {
+		Holds::<T, I>::get(who, reason)
+	}
+
+	fn hold_available(_reason: &Self::Reason, _who: &T::AccountId) -> bool {
+		true
+	}
+}
+
+impl<T: Config<I>, I: 'static> fungible::UnbalancedHold<T::AccountId> for Pallet<T, I> {
+	fn set_balance_on_hold(
+		reason: &Self::Reason,
+		who: &T::AccountId,
+		amount: Self::Balance,
+	) -> DispatchResult {
+		ensure!(!Paused::<T, I>::get(), Error::<T, I>::TokenPaused);
+		let held = Holds::<T, I>::get(who, reason);
+		let reserved = Reserved::<T, I>::get(who);
+		let reserved = if amount >= held {
+			reserved.checked_add(&(amount - held)).ok_or(Error::<T, I>::StorageOverflow)?
+		} else {
+			reserved.saturating_sub(held - amount)
+		};
+		if amount.is_zero() {
+			Holds::<T, I>::remove(who, reason);
+		} else {
+			Holds::<T, I>::insert(who, reason, amount);
+		}
+		Self::write_reserved(who, reserved);
+		Ok(())
+	}
+}
+
+impl<T: Config<I>, I: 'static> fungible::MutateHold<T::AccountId> for Pallet<T, I> {}
@@ -1,7 +1,22 @@
 #![cfg_attr(not(feature = "std"), no_std)]
 
 use frame_support::{
-	dispatch::Vec, pallet_prelude::DispatchResult, sp_runtime, sp_runtime::traits::StaticLookup,
+	dispatch::{GetDispatchInfo, PostDispatchInfo, Vec},
+	pallet_prelude::{DispatchResult, EnsureOrigin},
+	sp_runtime,
+	sp_runtime::{
+		traits::{
+			AtLeast32BitUnsigned, Convert, Dispatchable, IdentifyAccount, StaticLookup, Verify,
+			Zero,
+		},
+		DispatchError, FixedPointNumber, FixedPointOperand, FixedU128, Permill,
+	},
+	sp_std::boxed::Box,
+	traits::{BalanceStatus, Currency, Get, PalletInfoAccess, ReservableCurrency},
+};
+use frame_system::{
+	ensure_signed,
+	pallet_prelude::{BlockNumberFor, OriginFor},
 };
 
 /// ERC20 Pallet
@@ -14,82 +29,461 @@ mod mock;
 mod tests;
 
 pub mod benchmarking;
+mod impl_fungible;
+pub mod migrations;
+pub mod payment;
+pub mod runtime_api;
+pub mod types;
 pub mod weights;
+pub use types::*;
 pub use weights::*;
 
+/// Creates signed permits for the benchmarks of a runtime.
+#[cfg(feature = "runtime-benchmarks")]
+pub trait BenchmarkHelper<Public, AccountId, Signature> {
+	/// Returns a new signing key and the account it identifies.
+	fn signer() -> (Public, AccountId);
+	/// Signs `message` with the key of `signer`.
+	fn sign(signer: &Public, message: &[u8]) -> Signature;
+}
+
 /// A type alias for the account ID type used in the dispatchable functions of this pallet.
 type AccountIdLookupOf<T> = <<T as frame_system::Config>::Lookup as StaticLookup>::Source;
 
+/// Balance of the native currency reserved for allowance deposits.
+pub type DepositBalanceOf<T, I = ()> =
+	<<T as Config<I>>::Currency as Currency<<T as frame_system::Config>::AccountId>>::Balance;
+
 #[frame_support::pallet]
 pub mod pallet {
 	use super::*;
 	use frame_support::pallet_prelude::*;
 	use frame_system::pallet_prelude::*;
 
+	/// The current storage version.
+	const STORAGE_VERSION: StorageVersion = StorageVersion::new(6);
+
 	#[pallet::pallet]
-	pub struct Pallet<T>(_);
+	#[pallet::storage_version(STORAGE_VERSION)]
+	pub struct Pallet<T, I = ()>(_);
 
 	/// Configure the pallet by specifying the parameters and types on which it depends.
 	#[pallet::config]
-	pub trait Config: frame_system::Config {
+	pub trait Config<I: 'static = ()>: frame_system::Config {
 		/// Because this pallet emits events, it depends on the runtime's definition of an event.
-		type RuntimeEvent: From<Event<Self>> + IsType<<Self as frame_system::Config>::RuntimeEvent>;
+		type RuntimeEvent: From<Event<Self, I>>
+			+ IsType<<Self as frame_system::Config>::RuntimeEvent>;
+		/// The overarching call type, dispatched by `flash_mint`.
+		type RuntimeCall: Parameter
+			+ Dispatchable<RuntimeOrigin = Self::RuntimeOrigin, PostInfo = PostDispatchInfo>
+			+ GetDispatchInfo
+			+ From<frame_system::Call<Self>>
+			+ IsType<<Self as frame_system::Config>::RuntimeCall>;
 		/// Type representing the weight of this pallet
 		type WeightInfo: WeightInfo;
+		/// The balance type used for every token amount of this pallet.
+		type Balance: Member
+			+ Parameter
+			+ AtLeast32BitUnsigned
+			+ Default
+			+ Copy
+			+ MaybeSerializeDeserialize
+			+ MaxEncodedLen;
+
+		/// Minimum balance of an account. Accounts falling below it are removed and their dust is
+		/// burned.
+		#[pallet::constant]
+		type MinimumBalance: Get<Self::Balance>;
+
+		/// Maximum total supply, enforced on every mint but the issuance of `fungible::Balanced`.
+		#[pallet::constant]
+		type MaxSupply: Get<Self::Balance>;
 
+		/// Number of decimals used until `set_metadata` stores another value.
 		#[pallet::constant]
 		type Decimals: Get<u64>;
+
+		/// Maximum length of the token name and symbol.
+		#[pallet::constant]
+		type StringLimit: Get<u32>;
+
+		/// Origin allowed to change the token metadata.
+		type AdminOrigin: EnsureOrigin<Self::RuntimeOrigin>;
+
+		/// Origin allowed to grant and revoke any role, regardless of the role admins.
+		type RoleAdminOrigin: EnsureOrigin<Self::RuntimeOrigin>;
+
+		/// Maximum number of accounts holding the same role.
+		#[pallet::constant]
+		type MaxRoleMembers: Get<u32>;
+
+		/// Origin allowed to pause and unpause the token, besides holders of `Role::Pauser`.
+		type PauseOrigin: EnsureOrigin<Self::RuntimeOrigin>;
+
+		/// Origin allowed to freeze and thaw accounts.
+		type FreezeOrigin: EnsureOrigin<Self::RuntimeOrigin>;
+
+		/// Maximum number of transfers in one `batch_transfer`.
+		#[pallet::constant]
+		type MaxBatchSize: Get<u32>;
+
+		/// Maximum number of vesting schedules of an account.
+		#[pallet::constant]
+		type MaxVestingSchedules: Get<u32>;
+
+		/// Converts a number of blocks to the amount a `VestingSchedule` unlocks over them.
+		type BlockNumberToBalance: Convert<BlockNumberFor<Self>, Self::Balance>;
+
+		/// Maximum amount lent by one `flash_mint`, which cannot exceed the remaining mintable
+		/// supply either.
+		#[pallet::constant]
+		type MaxFlashMint: Get<Self::Balance>;
+
+		/// Fee charged on the amount lent by `flash_mint`.
+		#[pallet::constant]
+		type FlashMintFee: Get<Permill>;
+
+		/// Account receiving the `flash_mint` fees.
+		type FlashMintFeeReceiver: Get<Self::AccountId>;
+
+		/// Maximum transfer fee `set_transfer_fee` accepts, in basis points.
+		#[pallet::constant]
+		type MaxFeeBps: Get<u16>;
+
+		/// Account credited with the transfer fees. Fees too small to give it
+		/// `Config::MinimumBalance` are not charged.
+		type TransferFeeAccount: Get<Self::AccountId>;
+
+		/// Account receiving the transaction fees paid in the token, see `payment`.
+		type FeeCollector: Get<Self::AccountId>;
+
+		/// Token amount charged per unit of native transaction fee until `set_fee_rate` is
+		/// called.
+		#[pallet::constant]
+		type DefaultFeeRate: Get<FixedU128>;
+
+		/// Native currency in which allowance deposits are reserved.
+		type Currency: ReservableCurrency<Self::AccountId>;
+
+		/// Native deposit reserved from the owner for each allowance entry, released when the
+		/// allowance is removed.
+		#[pallet::constant]
+		type AllowanceDeposit: Get<DepositBalanceOf<Self, I>>;
+
+		/// Reasons other pallets put tokens on hold for, see `fungible::MutateHold`.
+		type RuntimeHoldReason: Parameter + Member + MaxEncodedLen + Ord + Copy;
+
+		/// Off-chain signature accepted by `permit`.
+		type OffchainSignature: Verify<Signer = Self::OffchainPublic> + Parameter;
+
+		/// Public key of `Config::OffchainSignature`, identifying the signing account.
+		type OffchainPublic: IdentifyAccount<AccountId = Self::AccountId>;
+
+		/// Creates signed permits in benchmarks.
+		#[cfg(feature = "runtime-benchmarks")]
+		type BenchmarkHelper: BenchmarkHelper<
+			Self::OffchainPublic,
+			Self::AccountId,
+			Self::OffchainSignature,
+		>;
 	}
 
 	// STORAGE
 
-	/// minters
+	/// role members
+	#[pallet::storage]
+	pub(super) type Roles<T: Config<I>, I: 'static = ()> =
+		StorageDoubleMap<_, Blake2_128Concat, Role, Blake2_128Concat, T::AccountId, ()>;
+
+	/// number of members of each role, bounded by `Config::MaxRoleMembers`
+	#[pallet::storage]
+	#[pallet::getter(fn role_member_count)]
+	pub(super) type RoleMemberCount<T: Config<I>, I: 'static = ()> =
+		StorageMap<_, Blake2_128Concat, Role, u32, ValueQuery>;
+
+	/// admin role of each role, `Role::Admin` if not set
+	#[pallet::storage]
+	pub(super) type RoleAdmins<T: Config<I>, I: 'static = ()> =
+		StorageMap<_, Blake2_128Concat, Role, Role>;
+
+	/// total supply
 	#[pallet::storage]
-	#[pallet::getter(fn minters)]
-	pub(super) type Minters<T: Config> = StorageMap<
+	#[pallet::getter(fn total_supply)]
+	pub(super) type TotalSupply<T: Config<I>, I: 'static = ()> =
+		StorageValue<_, T::Balance, ValueQuery>;
+
+	/// free balance, the part of the balance that can be transferred
+	#[pallet::storage]
+	#[pallet::getter(fn balance_of)]
+	pub(super) type Balances<T: Config<I>, I: 'static = ()> =
+		StorageMap<_, Blake2_128Concat, T::AccountId, T::Balance, ValueQuery>;
+
+	/// id of the latest snapshot, 0 before the first one
+	#[pallet::storage]
+	#[pallet::getter(fn current_snapshot_id)]
+	pub(super) type CurrentSnapshotId<T: Config<I>, I: 'static = ()> =
+		StorageValue<_, SnapshotId, ValueQuery>;
+
+	/// balance checkpoints of each account taken at snapshots, by index, reserved balance included
+	#[pallet::storage]
+	pub(super) type AccountSnapshots<T: Config<I>, I: 'static = ()> = StorageDoubleMap<
 		_,
 		Blake2_128Concat,
 		T::AccountId,
-		(),
-		ValueQuery, // return default value
+		Twox64Concat,
+		u32,
+		Checkpoint<T::Balance>,
 	>;
 
-	/// total supply
+	/// number of balance checkpoints of each account taken at snapshots
 	#[pallet::storage]
-	#[pallet::getter(fn total_supply)]
-	pub(super) type TotalSupply<T> = StorageValue<_, u64>;
+	pub(super) type NumAccountSnapshots<T: Config<I>, I: 'static = ()> =
+		StorageMap<_, Blake2_128Concat, T::AccountId, u32, ValueQuery>;
 
-	/// balance
+	/// total supply checkpoints taken at snapshots, by index
 	#[pallet::storage]
-	#[pallet::getter(fn balance_of)]
-	pub(super) type Balances<T: Config> =
-		StorageMap<_, Blake2_128Concat, T::AccountId, u64, ValueQuery>;
+	pub(super) type TotalSupplySnapshots<T: Config<I>, I: 'static = ()> =
+		StorageMap<_, Twox64Concat, u32, Checkpoint<T::Balance>>;
+
+	/// number of total supply checkpoints taken at snapshots
+	#[pallet::storage]
+	pub(super) type NumTotalSupplySnapshots<T: Config<I>, I: 'static = ()> =
+		StorageValue<_, u32, ValueQuery>;
+
+	/// account each account delegates its voting power to
+	#[pallet::storage]
+	#[pallet::getter(fn delegates)]
+	pub(super) type Delegates<T: Config<I>, I: 'static = ()> =
+		StorageMap<_, Blake2_128Concat, T::AccountId, T::AccountId>;
+
+	/// voting power checkpoints of each delegate, by index
+	#[pallet::storage]
+	pub(super) type VoteCheckpoints<T: Config<I>, I: 'static = ()> = StorageDoubleMap<
+		_,
+		Blake2_128Concat,
+		T::AccountId,
+		Twox64Concat,
+		u32,
+		VoteCheckpoint<T::Balance, BlockNumberFor<T>>,
+	>;
+
+	/// number of voting power checkpoints of each delegate
+	#[pallet::storage]
+	pub(super) type NumVoteCheckpoints<T: Config<I>, I: 'static = ()> =
+		StorageMap<_, Blake2_128Concat, T::AccountId, u32, ValueQuery>;
+
+	/// total supply checkpoints, by index
+	#[pallet::storage]
+	pub(super) type TotalSupplyCheckpoints<T: Config<I>, I: 'static = ()> =
+		StorageMap<_, Twox64Concat, u32, VoteCheckpoint<T::Balance, BlockNumberFor<T>>>;
+
+	/// number of total supply checkpoints
+	#[pallet::storage]
+	pub(super) type NumTotalSupplyCheckpoints<T: Config<I>, I: 'static = ()> =
+		StorageValue<_, u32, ValueQuery>;
+
+	/// vesting schedules of each account
+	#[pallet::storage]
+	#[pallet::getter(fn vesting)]
+	pub(super) type Vesting<T: Config<I>, I: 'static = ()> = StorageMap<
+		_,
+		Blake2_128Concat,
+		T::AccountId,
+		BoundedVec<VestingSchedule<T::Balance, BlockNumberFor<T>>, T::MaxVestingSchedules>,
+		ValueQuery,
+	>;
+
+	/// free balance locked by the vesting schedules, as of the last `vest` of the account
+	#[pallet::storage]
+	#[pallet::getter(fn locked)]
+	pub(super) type Locked<T: Config<I>, I: 'static = ()> =
+		StorageMap<_, Blake2_128Concat, T::AccountId, T::Balance, ValueQuery>;
+
+	/// reserved balance, including the balance on hold
+	#[pallet::storage]
+	#[pallet::getter(fn reserved_balance)]
+	pub(super) type Reserved<T: Config<I>, I: 'static = ()> =
+		StorageMap<_, Blake2_128Concat, T::AccountId, T::Balance, ValueQuery>;
+
+	/// part of the reserved balance on hold for each reason
+	#[pallet::storage]
+	pub(super) type Holds<T: Config<I>, I: 'static = ()> = StorageDoubleMap<
+		_,
+		Blake2_128Concat,
+		T::AccountId,
+		Blake2_128Concat,
+		T::RuntimeHoldReason,
+		T::Balance,
+		ValueQuery,
+	>;
 
 	/// allowances
 	#[pallet::storage]
 	#[pallet::getter(fn allowances)]
-	pub(super) type Allowances<T: Config> = StorageDoubleMap<
+	pub(super) type Allowances<T: Config<I>, I: 'static = ()> = StorageDoubleMap<
 		_,
 		Blake2_128Concat,
 		T::AccountId,
 		Blake2_128Concat,
 		T::AccountId,
-		u64,
+		T::Balance,
 		ValueQuery,
 	>;
 
+	/// account that paid the native deposit reserved for an allowance and the deposit, absent for
+	/// allowances created without one
+	#[pallet::storage]
+	pub(super) type AllowanceDeposits<T: Config<I>, I: 'static = ()> = StorageDoubleMap<
+		_,
+		Blake2_128Concat,
+		T::AccountId,
+		Blake2_128Concat,
+		T::AccountId,
+		(T::AccountId, DepositBalanceOf<T, I>),
+	>;
+
+	/// whether every token operation is paused
+	#[pallet::storage]
+	#[pallet::getter(fn paused)]
+	pub(super) type Paused<T: Config<I>, I: 'static = ()> = StorageValue<_, bool, ValueQuery>;
+
+	/// operations paused on their own
+	#[pallet::storage]
+	pub(super) type PausedOperations<T: Config<I>, I: 'static = ()> =
+		StorageMap<_, Blake2_128Concat, Operation, ()>;
+
+	/// accounts that can neither send, receive nor spend allowances
+	#[pallet::storage]
+	pub(super) type Frozen<T: Config<I>, I: 'static = ()> =
+		StorageMap<_, Blake2_128Concat, T::AccountId, ()>;
+
+	/// whether only allowlisted accounts and the fee accounts can receive tokens and transfer them
+	#[pallet::storage]
+	#[pallet::getter(fn allowlist_enabled)]
+	pub(super) type AllowlistEnabled<T: Config<I>, I: 'static = ()> =
+		StorageValue<_, bool, ValueQuery>;
+
+	/// accounts allowed to hold tokens while `AllowlistEnabled` is set
+	#[pallet::storage]
+	pub(super) type Allowlist<T: Config<I>, I: 'static = ()> =
+		StorageMap<_, Blake2_128Concat, T::AccountId, ()>;
+
+	/// fee charged on the transfer calls, in basis points of the amount
+	#[pallet::storage]
+	#[pallet::getter(fn transfer_fee_bps)]
+	pub(super) type TransferFeeBps<T: Config<I>, I: 'static = ()> =
+		StorageValue<_, u16, ValueQuery>;
+
+	/// accounts whose transfers, sent or received, are not charged the transfer fee
+	#[pallet::storage]
+	pub(super) type TransferFeeExempt<T: Config<I>, I: 'static = ()> =
+		StorageMap<_, Blake2_128Concat, T::AccountId, ()>;
+
+	/// mint quotas of minters, minters without a quota are only bounded by the supply cap
+	#[pallet::storage]
+	#[pallet::getter(fn mint_quota)]
+	pub(super) type MintQuotas<T: Config<I>, I: 'static = ()> =
+		StorageMap<_, Blake2_128Concat, T::AccountId, MintQuota<T::Balance, BlockNumberFor<T>>>;
+
+	/// nonce of the next permit signed by an account
+	#[pallet::storage]
+	#[pallet::getter(fn permit_nonce)]
+	pub(super) type PermitNonces<T: Config<I>, I: 'static = ()> =
+		StorageMap<_, Blake2_128Concat, T::AccountId, u64, ValueQuery>;
+
+	/// token amount charged per unit of native transaction fee, `Config::DefaultFeeRate` until set
+	#[pallet::storage]
+	#[pallet::getter(fn fee_rate)]
+	pub(super) type FeeRate<T: Config<I>, I: 'static = ()> =
+		StorageValue<_, FixedU128, ValueQuery, T::DefaultFeeRate>;
+
+	/// token name
+	#[pallet::storage]
+	#[pallet::getter(fn name)]
+	pub(super) type Name<T: Config<I>, I: 'static = ()> =
+		StorageValue<_, BoundedVec<u8, T::StringLimit>, ValueQuery>;
+
+	/// token symbol
+	#[pallet::storage]
+	#[pallet::getter(fn symbol)]
+	pub(super) type Symbol<T: Config<I>, I: 'static = ()> =
+		StorageValue<_, BoundedVec<u8, T::StringLimit>, ValueQuery>;
+
+	/// token decimals, `Config::Decimals` until set
+	#[pallet::storage]
+	#[pallet::getter(fn decimals)]
+	pub(super) type Decimals<T: Config<I>, I: 'static = ()> =
+		StorageValue<_, u64, ValueQuery, T::Decimals>;
+
 	#[pallet::genesis_config]
-	#[derive(frame_support::DefaultNoBound)] // make empty minters by default
-	pub struct GenesisConfig<T: Config> {
+	#[derive(frame_support::DefaultNoBound)] // make empty roles by default
+	pub struct GenesisConfig<T: Config<I>, I: 'static = ()> {
+		pub admins: Vec<T::AccountId>,
 		pub minters: Vec<T::AccountId>,
+		pub burners: Vec<T::AccountId>,
+		pub pausers: Vec<T::AccountId>,
+		/// Initial balances, must not exceed `Config::MaxSupply` in total.
+		pub balances: Vec<(T::AccountId, T::Balance)>,
+		pub name: Vec<u8>,
+		pub symbol: Vec<u8>,
+		/// Falls back to `Config::Decimals` when not set.
+		pub decimals: Option<u64>,
+		/// Vesting schedules as `(account, start, cliff, per_block, locked)`, locking part of the
+		/// initial balance of the account.
+		pub vesting:
+			Vec<(T::AccountId, BlockNumberFor<T>, BlockNumberFor<T>, T::Balance, T::Balance)>,
+		#[serde(skip)]
+		pub phantom: PhantomData<I>,
 	}
 
 	// The build of genesis for the pallet.
 	#[pallet::genesis_build]
-	impl<T: Config> BuildGenesisConfig for GenesisConfig<T> {
+	impl<T: Config<I>, I: 'static> BuildGenesisConfig for GenesisConfig<T, I> {
 		fn build(&self) {
-			for admin in &self.minters {
-				<Minters<T>>::insert(admin, ());
+			let members = [
+				(Role::Admin, &self.admins),
+				(Role::Minter, &self.minters),
+				(Role::Burner, &self.burners),
+				(Role::Pauser, &self.pausers),
+			];
+			for (role, accounts) in members {
+				for who in accounts {
+					Pallet::<T, I>::insert_role_member(role, who)
+						.expect("too many genesis role members");
+				}
+			}
+
+			let name: BoundedVec<u8, T::StringLimit> =
+				self.name.clone().try_into().expect("token name is too long");
+			let symbol: BoundedVec<u8, T::StringLimit> =
+				self.symbol.clone().try_into().expect("token symbol is too long");
+			<Name<T, I>>::put(name);
+			<Symbol<T, I>>::put(symbol);
+			if let Some(decimals) = self.decimals {
+				<Decimals<T, I>>::put(decimals);
+			}
+
+			for (who, balance) in &self.balances {
+				Pallet::<T, I>::_mint(who.clone(), *balance).unwrap_or_else(|error| match error {
+					Error::<T, I>::BelowMinimumBalance =>
+						panic!("genesis balances must not be below the minimum balance"),
+					_ => panic!("genesis balances must not exceed the supply cap"),
+				});
+			}
+
+			for (who, start, cliff, per_block, locked) in &self.vesting {
+				let schedule = VestingSchedule {
+					locked: *locked,
+					per_block: *per_block,
+					start: *start,
+					cliff: *cliff,
+				};
+				Pallet::<T, I>::add_vesting_schedule(who, schedule)
+					.expect("invalid genesis vesting schedule");
+				assert!(
+					Locked::<T, I>::get(who) <= Balances::<T, I>::get(who),
+					"genesis vesting schedules must not lock more than the balance"
+				);
 			}
 		}
 	}
@@ -97,34 +491,259 @@ pub mod pallet {
 	// EVENTS
 	#[pallet::event]
 	#[pallet::generate_deposit(pub(super) fn deposit_event)]
-	pub enum Event<T: Config> {
-		Transfer { from: T::AccountId, to: T::AccountId, value: u64 },
-		Approval { owner: T::AccountId, spender: T::AccountId, value: u64 },
+	pub enum Event<T: Config<I>, I: 'static = ()> {
+		/// `value` tokens moved from `from` to `to`. A transfer charged a fee emits one
+		/// `Transfer` of the fee to `Config::TransferFeeAccount`, one of the amount the recipient
+		/// receives, then `FeeCharged`.
+		Transfer {
+			from: T::AccountId,
+			to: T::AccountId,
+			value: T::Balance,
+		},
+		Approval {
+			owner: T::AccountId,
+			spender: T::AccountId,
+			value: T::Balance,
+		},
+		/// `from` sent `total` tokens to `count` recipients in one `batch_transfer`, each
+		/// recipient has its own `Transfer` event.
+		BatchTransferred {
+			from: T::AccountId,
+			count: u32,
+			total: T::Balance,
+		},
+		/// The rate converting native transaction fees to the token was set.
+		FeeRateSet {
+			rate: FixedU128,
+		},
+		/// The transfer fee was set to `bps` basis points.
+		TransferFeeSet {
+			bps: u16,
+		},
+		/// `account` was exempted from the transfer fee, or no longer is.
+		TransferFeeExemptionSet {
+			account: T::AccountId,
+			exempt: bool,
+		},
+		/// `from` paid a transfer fee of `fee` tokens on its transfer to `to`.
+		FeeCharged {
+			from: T::AccountId,
+			to: T::AccountId,
+			fee: T::Balance,
+		},
+		/// `who` paid a transaction fee of `fee` tokens, tip included.
+		TransactionFeePaid {
+			who: T::AccountId,
+			fee: T::Balance,
+		},
+		/// `account` was removed below `Config::MinimumBalance` and its remaining `amount` burned.
+		DustLost {
+			account: T::AccountId,
+			amount: T::Balance,
+		},
+		/// Snapshot `id` of the balances and total supply was taken.
+		Snapshot {
+			id: SnapshotId,
+		},
+		/// `delegator` delegates its voting power to `to_delegate` instead of `from_delegate`.
+		DelegateChanged {
+			delegator: T::AccountId,
+			from_delegate: Option<T::AccountId>,
+			to_delegate: T::AccountId,
+		},
+		/// The voting power of `delegate` changed.
+		DelegateVotesChanged {
+			delegate: T::AccountId,
+			previous_votes: T::Balance,
+			new_votes: T::Balance,
+		},
+		/// `borrower` repaid the `amount` lent by `flash_mint` and paid `fee`.
+		FlashMinted {
+			borrower: T::AccountId,
+			amount: T::Balance,
+			fee: T::Balance,
+		},
+		/// `schedule` now locks tokens of `account`.
+		VestingScheduleAdded {
+			account: T::AccountId,
+			schedule: VestingSchedule<T::Balance, BlockNumberFor<T>>,
+		},
+		/// `account` vested, `unvested` tokens are still locked.
+		VestingUpdated {
+			account: T::AccountId,
+			unvested: T::Balance,
+		},
+		/// Every vesting schedule of `account` is over.
+		VestingCompleted {
+			account: T::AccountId,
+		},
+		/// `value` tokens of `who` were moved from its free to its reserved balance.
+		Reserved {
+			who: T::AccountId,
+			value: T::Balance,
+		},
+		/// `value` tokens of `who` were moved from its reserved to its free balance.
+		Unreserved {
+			who: T::AccountId,
+			value: T::Balance,
+		},
+		/// `value` reserved tokens of `from` were moved to the balance of `to` given by
+		/// `destination_status`.
+		ReserveRepatriated {
+			from: T::AccountId,
+			to: T::AccountId,
+			value: T::Balance,
+			destination_status: BalanceStatus,
+		},
+		/// `value` tokens of `from` were burned.
+		Burned {
+			from: T::AccountId,
+			value: T::Balance,
+		},
+		/// Total supply was recomputed from the balances by a storage migration.
+		TotalSupplyRecomputed {
+			total_supply: T::Balance,
+		},
+		/// Token metadata was changed by the admin origin.
+		MetadataSet {
+			name: Vec<u8>,
+			symbol: Vec<u8>,
+			decimals: u64,
+		},
+		/// `account` was granted `role`. `sender` is `None` for `Config::RoleAdminOrigin`.
+		RoleGranted {
+			role: Role,
+			account: T::AccountId,
+			sender: Option<T::AccountId>,
+		},
+		/// `role` was revoked from `account`. `sender` is `None` for `Config::RoleAdminOrigin`.
+		RoleRevoked {
+			role: Role,
+			account: T::AccountId,
+			sender: Option<T::AccountId>,
+		},
+		/// The admin role of `role` was changed.
+		RoleAdminChanged {
+			role: Role,
+			previous_admin_role: Role,
+			new_admin_role: Role,
+		},
+		/// `operation` was paused, or every operation if `None`.
+		Paused {
+			operation: Option<Operation>,
+		},
+		/// `operation` was unpaused, or the global pause lifted if `None`.
+		Unpaused {
+			operation: Option<Operation>,
+		},
+		/// `account` was frozen.
+		AccountFrozen {
+			account: T::AccountId,
+		},
+		/// `account` was thawed.
+		AccountThawed {
+			account: T::AccountId,
+		},
+		/// `account` was added to the allowlist.
+		AddedToAllowlist {
+			account: T::AccountId,
+		},
+		/// `account` was removed from the allowlist.
+		RemovedFromAllowlist {
+			account: T::AccountId,
+		},
+		/// The allowlist was enabled or disabled.
+		AllowlistEnabledSet {
+			enabled: bool,
+		},
+		/// The mint quota of `minter` was set to `allowance`, limited to `max_per_period` every
+		/// `period` blocks if `rate_limit` is set.
+		MintQuotaSet {
+			minter: T::AccountId,
+			allowance: T::Balance,
+			rate_limit: Option<(T::Balance, BlockNumberFor<T>)>,
+		},
+		/// The mint quota of `minter` was removed.
+		MintQuotaCleared {
+			minter: T::AccountId,
+		},
 	}
 
 	// ERRORS.
 	#[pallet::error]
-	pub enum Error<T> {
+	pub enum Error<T, I = ()> {
 		NoneValue,
 		StorageOverflow,
-		AccessControl,
+		/// The caller is missing the given role.
+		AccessControl(Role),
 		ERC20InsufficientBalance,
 		ERC20InsufficientAllowance,
+		/// Name or symbol is longer than `Config::StringLimit`.
+		BadMetadata,
+		/// `Config::MaxRoleMembers` was reached.
+		TooManyRoleMembers,
+		/// The operation is paused.
+		TokenPaused,
+		/// Minting would exceed `Config::MaxSupply`.
+		SupplyCapExceeded,
+		/// Minting would exceed the remaining mint quota or the amount allowed in the current
+		/// period.
+		MintQuotaExceeded,
+		/// The period of a mint rate limit is zero.
+		InvalidMintPeriod,
+		/// Decreasing the allowance would take it below zero.
+		AllowanceBelowZero,
+		/// The deadline of the permit has passed.
+		PermitExpired,
+		/// The permit is not signed by the owner, or not for the current nonce.
+		InvalidPermitSignature,
+		/// The batch holds more than `Config::MaxBatchSize` transfers.
+		TooManyTransfers,
+		/// The balance of the recipient would be below `Config::MinimumBalance`.
+		BelowMinimumBalance,
+		/// The transfer would remove the sender, which was asked to be kept alive.
+		WouldReapAccount,
+		/// The caller has no allowance entry for the spender.
+		NoAllowance,
+		/// The vesting schedule locks nothing or unlocks nothing per block.
+		InvalidVestingSchedule,
+		/// The account already has `Config::MaxVestingSchedules` vesting schedules.
+		TooManyVestingSchedules,
+		/// The account has no vesting schedule.
+		NotVesting,
+		/// The balance would fall below the amount locked by vesting.
+		BalanceLocked,
+		/// No snapshot with this id was taken.
+		NonexistentSnapshot,
+		/// Past votes and total supply can only be read for past blocks.
+		FutureLookup,
+		/// The amount exceeds the maximum `flash_mint` loan.
+		ExceededMaxFlashMint,
+		/// The borrower cannot repay the `flash_mint` loan and its fee.
+		FlashMintNotRepaid,
+		/// The account is frozen.
+		AccountFrozen,
+		/// The account is not on the allowlist while it is enabled.
+		NotAllowlisted,
+		/// More accounts than `Config::MaxBatchSize` were given.
+		TooManyAccounts,
+		/// The transfer fee exceeds `Config::MaxFeeBps`.
+		TransferFeeTooHigh,
 	}
 
 	// FUNCTIONS
 	#[pallet::call]
-	impl<T: Config> Pallet<T> {
+	impl<T: Config<I>, I: 'static> Pallet<T, I> {
 		#[pallet::call_index(0)]
 		#[pallet::weight(T::WeightInfo::transfer())]
 		pub fn transfer(
 			origin: OriginFor<T>,
 			to: AccountIdLookupOf<T>,
-			value: u64,
+			value: T::Balance,
 		) -> DispatchResult {
 			let sender = ensure_signed(origin)?;
 			let to = T::Lookup::lookup(to)?;
-			Self::_transfer(sender, to, value)?;
+			Self::transfer_with_fee(sender, to, value, false)?;
 			Ok(())
 		}
 
@@ -134,13 +753,13 @@ pub mod pallet {
 			origin: OriginFor<T>,
 			from: AccountIdLookupOf<T>,
 			to: AccountIdLookupOf<T>,
-			value: u64,
+			value: T::Balance,
 		) -> DispatchResult {
 			let spender = ensure_signed(origin)?;
 			let from = T::Lookup::lookup(from)?;
 			let to = T::Lookup::lookup(to)?;
 			Self::_spend_allowance(from.clone(), spender, value)?;
-			Self::_transfer(from, to, value)?;
+			Self::transfer_with_fee(from, to, value, false)?;
 			Ok(())
 		}
 
@@ -149,88 +768,1392 @@ pub mod pallet {
 		pub fn approve(
 			origin: OriginFor<T>,
 			spender: AccountIdLookupOf<T>,
-			value: u64,
+			value: T::Balance,
 		) -> DispatchResult {
 			let owner = ensure_signed(origin)?;
 			let spender = T::Lookup::lookup(spender)?;
-			Self::_approve(owner, spender, value);
+			Self::_approve(owner, spender, value)?;
 			Ok(())
 		}
 
 		#[pallet::call_index(3)]
 		#[pallet::weight(T::WeightInfo::mint())]
-		pub fn mint(origin: OriginFor<T>, value: u64) -> DispatchResult {
+		pub fn mint(origin: OriginFor<T>, value: T::Balance) -> DispatchResult {
 			let _who = ensure_signed(origin)?;
-			if !Minters::<T>::contains_key(_who.clone()) {
-				return Err(Error::<T>::AccessControl.into())
-			}
+			Self::_check_role(Role::Minter, &_who)?;
+			Self::use_mint_quota(&_who, value)?;
 			Self::_mint(_who, value)?;
 			Ok(())
 		}
 
 		#[pallet::call_index(4)]
 		#[pallet::weight(T::WeightInfo::burn())]
-		pub fn burn(origin: OriginFor<T>, value: u64) -> DispatchResult {
+		pub fn burn(origin: OriginFor<T>, value: T::Balance) -> DispatchResult {
 			let _who = ensure_signed(origin)?;
+			Self::_check_role(Role::Burner, &_who)?;
 			Self::_burn(_who, value)?;
 			Ok(())
 		}
+
+		#[pallet::call_index(5)]
+		#[pallet::weight(T::WeightInfo::set_metadata())]
+		pub fn set_metadata(
+			origin: OriginFor<T>,
+			name: Vec<u8>,
+			symbol: Vec<u8>,
+			decimals: u64,
+		) -> DispatchResult {
+			T::AdminOrigin::ensure_origin(origin)?;
+			let bounded_name: BoundedVec<u8, T::StringLimit> =
+				name.clone().try_into().map_err(|_| Error::<T, I>::BadMetadata)?;
+			let bounded_symbol: BoundedVec<u8, T::StringLimit> =
+				symbol.clone().try_into().map_err(|_| Error::<T, I>::BadMetadata)?;
+
+			Name::<T, I>::put(bounded_name);
+			Symbol::<T, I>::put(bounded_symbol);
+			Decimals::<T, I>::put(decimals);
+			Self::deposit_event(Event::<T, I>::MetadataSet { name, symbol, decimals });
+			Ok(())
+		}
+
+		/// Grants `Role::Minter` to `who`, see `grant_role`.
+		#[pallet::call_index(6)]
+		#[pallet::weight(T::WeightInfo::grant_role())]
+		pub fn add_minter(origin: OriginFor<T>, who: AccountIdLookupOf<T>) -> DispatchResult {
+			Self::grant_role(origin, Role::Minter, who)
+		}
+
+		/// Revokes `Role::Minter` from `who`, see `revoke_role`.
+		#[pallet::call_index(7)]
+		#[pallet::weight(T::WeightInfo::revoke_role())]
+		pub fn remove_minter(origin: OriginFor<T>, who: AccountIdLookupOf<T>) -> DispatchResult {
+			Self::revoke_role(origin, Role::Minter, who)
+		}
+
+		/// Grants `role` to `who`. The caller must hold the admin role of `role` or be
+		/// `Config::RoleAdminOrigin`.
+		#[pallet::call_index(8)]
+		#[pallet::weight(T::WeightInfo::grant_role())]
+		pub fn grant_role(
+			origin: OriginFor<T>,
+			role: Role,
+			who: AccountIdLookupOf<T>,
+		) -> DispatchResult {
+			let sender = Self::ensure_role_admin(origin, role)?;
+			let who = T::Lookup::lookup(who)?;
+			Self::_grant_role(role, who, sender)?;
+			Ok(())
+		}
+
+		/// Revokes `role` from `who`. The caller must hold the admin role of `role` or be
+		/// `Config::RoleAdminOrigin`.
+		#[pallet::call_index(9)]
+		#[pallet::weight(T::WeightInfo::revoke_role())]
+		pub fn revoke_role(
+			origin: OriginFor<T>,
+			role: Role,
+			who: AccountIdLookupOf<T>,
+		) -> DispatchResult {
+			let sender = Self::ensure_role_admin(origin, role)?;
+			let who = T::Lookup::lookup(who)?;
+			Self::_revoke_role(role, who, sender);
+			Ok(())
+		}
+
+		/// Gives up `role` held by the caller.
+		#[pallet::call_index(10)]
+		#[pallet::weight(T::WeightInfo::revoke_role())]
+		pub fn renounce_role(origin: OriginFor<T>, role: Role) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+			Self::_revoke_role(role, who.clone(), Some(who));
+			Ok(())
+		}
+
+		/// Makes `admin_role` the role administering `role`.
+		#[pallet::call_index(11)]
+		#[pallet::weight(T::WeightInfo::set_role_admin())]
+		pub fn set_role_admin(
+			origin: OriginFor<T>,
+			role: Role,
+			admin_role: Role,
+		) -> DispatchResult {
+			T::RoleAdminOrigin::ensure_origin(origin)?;
+			let previous_admin_role = Self::role_admin(role);
+			RoleAdmins::<T, I>::insert(role, admin_role);
+			Self::deposit_event(Event::<T, I>::RoleAdminChanged {
+				role,
+				previous_admin_role,
+				new_admin_role: admin_role,
+			});
+			Ok(())
+		}
+
+		/// Pauses `operation`, or every operation if `None`. The caller must hold `Role::Pauser`
+		/// or be `Config::PauseOrigin`.
+		#[pallet::call_index(12)]
+		#[pallet::weight(T::WeightInfo::pause())]
+		pub fn pause(origin: OriginFor<T>, operation: Option<Operation>) -> DispatchResult {
+			Self::ensure_pauser(origin)?;
+			match operation {
+				Some(operation) => PausedOperations::<T, I>::insert(operation, ()),
+				None => Paused::<T, I>::put(true),
+			}
+			Self::deposit_event(Event::<T, I>::Paused { operation });
+			Ok(())
+		}
+
+		/// Unpauses `operation`, or lifts the global pause if `None`. Operations paused on their
+		/// own stay paused until unpaused explicitly.
+		#[pallet::call_index(13)]
+		#[pallet::weight(T::WeightInfo::unpause())]
+		pub fn unpause(origin: OriginFor<T>, operation: Option<Operation>) -> DispatchResult {
+			Self::ensure_pauser(origin)?;
+			match operation {
+				Some(operation) => PausedOperations::<T, I>::remove(operation),
+				None => Paused::<T, I>::kill(),
+			}
+			Self::deposit_event(Event::<T, I>::Unpaused { operation });
+			Ok(())
+		}
+
+		/// Sets the mint quota of `minter` to `allowance`, optionally limited to `max_per_period`
+		/// every `period` blocks. Calling it again refills the quota and starts a new period. The
+		/// caller must hold the admin role of `Role::Minter` or be `Config::RoleAdminOrigin`.
+		#[pallet::call_index(14)]
+		#[pallet::weight(T::WeightInfo::set_mint_quota())]
+		pub fn set_mint_quota(
+			origin: OriginFor<T>,
+			minter: AccountIdLookupOf<T>,
+			allowance: T::Balance,
+			rate_limit: Option<(T::Balance, BlockNumberFor<T>)>,
+		) -> DispatchResult {
+			Self::ensure_role_admin(origin, Role::Minter)?;
+			let minter = T::Lookup::lookup(minter)?;
+			let now = frame_system::Pallet::<T>::block_number();
+			let limit = match rate_limit {
+				Some((max_per_period, period)) => {
+					ensure!(!period.is_zero(), Error::<T, I>::InvalidMintPeriod);
+					Some(MintRateLimit {
+						max_per_period,
+						period,
+						period_start: now,
+						minted: Zero::zero(),
+					})
+				},
+				None => None,
+			};
+			MintQuotas::<T, I>::insert(
+				&minter,
+				MintQuota { remaining: allowance, rate_limit: limit },
+			);
+			Self::deposit_event(Event::<T, I>::MintQuotaSet { minter, allowance, rate_limit });
+			Ok(())
+		}
+
+		/// Removes the mint quota of `minter`, leaving it bounded by the supply cap only. The
+		/// caller must hold the admin role of `Role::Minter` or be `Config::RoleAdminOrigin`.
+		#[pallet::call_index(15)]
+		#[pallet::weight(T::WeightInfo::clear_mint_quota())]
+		pub fn clear_mint_quota(
+			origin: OriginFor<T>,
+			minter: AccountIdLookupOf<T>,
+		) -> DispatchResult {
+			Self::ensure_role_admin(origin, Role::Minter)?;
+			let minter = T::Lookup::lookup(minter)?;
+			if MintQuotas::<T, I>::take(&minter).is_some() {
+				Self::deposit_event(Event::<T, I>::MintQuotaCleared { minter });
+			}
+			Ok(())
+		}
+
+		/// Raises the allowance of `spender` over the caller's tokens by `added_value`.
+		#[pallet::call_index(16)]
+		#[pallet::weight(T::WeightInfo::increase_allowance())]
+		pub fn increase_allowance(
+			origin: OriginFor<T>,
+			spender: AccountIdLookupOf<T>,
+			added_value: T::Balance,
+		) -> DispatchResult {
+			let owner = ensure_signed(origin)?;
+			let spender = T::Lookup::lookup(spender)?;
+			let new_allowance = Allowances::<T, I>::get(owner.clone(), spender.clone())
+				.checked_add(&added_value)
+				.ok_or(Error::<T, I>::StorageOverflow)?;
+			Self::_approve(owner, spender, new_allowance)?;
+			Ok(())
+		}
+
+		/// Lowers the allowance of `spender` over the caller's tokens by `subtracted_value`.
+		#[pallet::call_index(17)]
+		#[pallet::weight(T::WeightInfo::decrease_allowance())]
+		pub fn decrease_allowance(
+			origin: OriginFor<T>,
+			spender: AccountIdLookupOf<T>,
+			subtracted_value: T::Balance,
+		) -> DispatchResult {
+			let owner = ensure_signed(origin)?;
+			let spender = T::Lookup::lookup(spender)?;
+			let new_allowance = Allowances::<T, I>::get(owner.clone(), spender.clone())
+				.checked_sub(&subtracted_value)
+				.ok_or(Error::<T, I>::AllowanceBelowZero)?;
+			Self::_approve(owner, spender, new_allowance)?;
+			Ok(())
+		}
+
+		/// Burns `value` tokens of `from`, taken from the allowance of the caller. The caller must
+		/// hold `Role::Burner`.
+		#[pallet::call_index(18)]
+		#[pallet::weight(T::WeightInfo::burn_from())]
+		pub fn burn_from(
+			origin: OriginFor<T>,
+			from: AccountIdLookupOf<T>,
+			value: T::Balance,
+		) -> DispatchResult {
+			let spender = ensure_signed(origin)?;
+			Self::_check_role(Role::Burner, &spender)?;
+			let from = T::Lookup::lookup(from)?;
+			Self::_spend_allowance(from.clone(), spender, value)?;
+			Self::_burn(from, value)?;
+			Ok(())
+		}
+
+		/// Sets the allowance of `spender` over the tokens of `owner` to `value`, authorised by
+		/// the owner's `signature` of `Pallet::permit_payload` instead of an `approve` call. Can
+		/// be submitted by anyone until the `deadline` block, the submitter paying the deposit of
+		/// a new allowance entry.
+		#[pallet::call_index(19)]
+		#[pallet::weight(T::WeightInfo::permit())]
+		pub fn permit(
+			origin: OriginFor<T>,
+			owner: AccountIdLookupOf<T>,
+			spender: AccountIdLookupOf<T>,
+			value: T::Balance,
+			deadline: BlockNumberFor<T>,
+			signature: T::OffchainSignature,
+		) -> DispatchResult {
+			let sender = ensure_signed(origin)?;
+			let owner = T::Lookup::lookup(owner)?;
+			let spender = T::Lookup::lookup(spender)?;
+			ensure!(
+				frame_system::Pallet::<T>::block_number() <= deadline,
+				Error::<T, I>::PermitExpired
+			);
+
+			let payload = Self::permit_payload(owner.clone(), spender.clone(), value, deadline);
+			ensure!(
+				signature.verify(&payload.encode()[..], &owner),
+				Error::<T, I>::InvalidPermitSignature
+			);
+			PermitNonces::<T, I>::mutate(&owner, |nonce| *nonce = nonce.saturating_add(1));
+			Self::approve_with_depositor(owner, spender, value, &sender)?;
+			Ok(())
+		}
+
+		/// Transfers tokens of the caller to every recipient of `transfers`. Either all transfers
+		/// succeed or none is applied.
+		#[pallet::call_index(20)]
+		#[pallet::weight(T::WeightInfo::batch_transfer(transfers.len() as u32))]
+		pub fn batch_transfer(
+			origin: OriginFor<T>,
+			transfers: Vec<(AccountIdLookupOf<T>, T::Balance)>,
+		) -> DispatchResult {
+			let sender = ensure_signed(origin)?;
+			ensure!(
+				transfers.len() <= T::MaxBatchSize::get() as usize,
+				Error::<T, I>::TooManyTransfers
+			);
+
+			let count = transfers.len() as u32;
+			let mut total = T::Balance::zero();
+			for (to, value) in transfers {
+				let to = T::Lookup::lookup(to)?;
+				total = total.checked_add(&value).ok_or(Error::<T, I>::StorageOverflow)?;
+				Self::transfer_with_fee(sender.clone(), to, value, false)?;
+			}
+			Self::deposit_event(Event::<T, I>::BatchTransferred { from: sender, count, total });
+			Ok(())
+		}
+
+		/// Sets the token amount charged per unit of native transaction fee.
+		#[pallet::call_index(21)]
+		#[pallet::weight(T::WeightInfo::set_fee_rate())]
+		pub fn set_fee_rate(origin: OriginFor<T>, rate: FixedU128) -> DispatchResult {
+			T::AdminOrigin::ensure_origin(origin)?;
+			FeeRate::<T, I>::put(rate);
+			Self::deposit_event(Event::<T, I>::FeeRateSet { rate });
+			Ok(())
+		}
+
+		/// Same as `transfer`, but fails instead of removing the caller if its balance would fall
+		/// below `Config::MinimumBalance`.
+		#[pallet::call_index(22)]
+		#[pallet::weight(T::WeightInfo::transfer_keep_alive())]
+		pub fn transfer_keep_alive(
+			origin: OriginFor<T>,
+			to: AccountIdLookupOf<T>,
+			value: T::Balance,
+		) -> DispatchResult {
+			let sender = ensure_signed(origin)?;
+			let to = T::Lookup::lookup(to)?;
+			Self::transfer_with_fee(sender, to, value, true)?;
+			Ok(())
+		}
+
+		/// Removes the allowance of `spender` over the caller's tokens and releases its deposit.
+		#[pallet::call_index(23)]
+		#[pallet::weight(T::WeightInfo::remove_allowance())]
+		pub fn remove_allowance(
+			origin: OriginFor<T>,
+			spender: AccountIdLookupOf<T>,
+		) -> DispatchResult {
+			let owner = ensure_signed(origin)?;
+			let spender = T::Lookup::lookup(spender)?;
+			ensure!(Allowances::<T, I>::contains_key(&owner, &spender), Error::<T, I>::NoAllowance);
+			Self::_approve(owner, spender, Zero::zero())?;
+			Ok(())
+		}
+
+		/// Transfers `schedule.locked` tokens of the caller to `target`, unlocked over time by
+		/// `schedule`. The caller must hold `Role::Minter` or `Role::Admin`.
+		#[pallet::call_index(24)]
+		#[pallet::weight(T::WeightInfo::vested_transfer())]
+		pub fn vested_transfer(
+			origin: OriginFor<T>,
+			target: AccountIdLookupOf<T>,
+			schedule: VestingSchedule<T::Balance, BlockNumberFor<T>>,
+		) -> DispatchResult {
+			let sender = ensure_signed(origin)?;
+			if !Self::has_role(Role::Admin, &sender) {
+				Self::_check_role(Role::Minter, &sender)?;
+			}
+			let target = T::Lookup::lookup(target)?;
+			Self::_transfer(sender, target.clone(), schedule.locked, false)?;
+			Self::add_vesting_schedule(&target, schedule)?;
+			Ok(())
+		}
+
+		/// Unlocks the tokens of the caller vested so far.
+		#[pallet::call_index(25)]
+		#[pallet::weight(T::WeightInfo::vest())]
+		pub fn vest(origin: OriginFor<T>) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+			ensure!(Vesting::<T, I>::contains_key(&who), Error::<T, I>::NotVesting);
+			let unvested = Self::update_vesting_lock(&who);
+			if unvested.is_zero() {
+				Self::deposit_event(Event::<T, I>::VestingCompleted { account: who });
+			} else {
+				Self::deposit_event(Event::<T, I>::VestingUpdated { account: who, unvested });
+			}
+			Ok(())
+		}
+
+		/// Takes a snapshot of the balances and total supply, readable through `balance_of_at`
+		/// and `total_supply_at` with the id of the `Snapshot` event.
+		#[pallet::call_index(26)]
+		#[pallet::weight(T::WeightInfo::snapshot())]
+		pub fn snapshot(origin: OriginFor<T>) -> DispatchResult {
+			T::AdminOrigin::ensure_origin(origin)?;
+			let id = CurrentSnapshotId::<T, I>::get()
+				.checked_add(1)
+				.ok_or(Error::<T, I>::StorageOverflow)?;
+			CurrentSnapshotId::<T, I>::put(id);
+			Self::deposit_event(Event::<T, I>::Snapshot { id });
+			Ok(())
+		}
+
+		/// Delegates the voting power of the balance of the caller, reserved balance included, to
+		/// `delegatee`, which can be the caller itself.
+		#[pallet::call_index(27)]
+		#[pallet::weight(T::WeightInfo::delegate())]
+		pub fn delegate(origin: OriginFor<T>, delegatee: AccountIdLookupOf<T>) -> DispatchResult {
+			let delegator = ensure_signed(origin)?;
+			let delegatee = T::Lookup::lookup(delegatee)?;
+			Self::_delegate(delegator, delegatee);
+			Ok(())
+		}
+
+		/// Mints `amount` to the caller, dispatches `call` from the caller, then burns `amount`
+		/// back and takes the `Config::FlashMintFee`, reverting everything if the caller cannot
+		/// repay them.
+		#[pallet::call_index(28)]
+		#[pallet::weight({
+			let dispatch_info = call.get_dispatch_info();
+			(T::WeightInfo::flash_mint().saturating_add(dispatch_info.weight), dispatch_info.class)
+		})]
+		pub fn flash_mint(
+			origin: OriginFor<T>,
+			amount: T::Balance,
+			call: Box<<T as Config<I>>::RuntimeCall>,
+		) -> DispatchResultWithPostInfo {
+			let borrower = ensure_signed(origin)?;
+			ensure!(amount <= Self::max_flash_mint(), Error::<T, I>::ExceededMaxFlashMint);
+			let fee = Self::flash_mint_fee(amount);
+			Self::_mint(borrower.clone(), amount)?;
+
+			let dispatch_info = call.get_dispatch_info();
+			let post_info = call
+				.dispatch(frame_system::RawOrigin::Signed(borrower.clone()).into())
+				.map_err(|err| err.error)?;
+
+			Self::_burn(borrower.clone(), amount).map_err(Self::not_repaid)?;
+			if !fee.is_zero() {
+				Self::_transfer(borrower.clone(), T::FlashMintFeeReceiver::get(), fee, false)
+					.map_err(Self::not_repaid)?;
+			}
+			Self::deposit_event(Event::<T, I>::FlashMinted { borrower, amount, fee });
+
+			let weight = T::WeightInfo::flash_mint()
+				.saturating_add(post_info.calc_actual_weight(&dispatch_info));
+			Ok(Some(weight).into())
+		}
+
+		/// Freezes `who`, which can then neither send, receive nor spend allowances.
+		#[pallet::call_index(29)]
+		#[pallet::weight(T::WeightInfo::freeze_account())]
+		pub fn freeze_account(origin: OriginFor<T>, who: AccountIdLookupOf<T>) -> DispatchResult {
+			T::FreezeOrigin::ensure_origin(origin)?;
+			let who = T::Lookup::lookup(who)?;
+			Frozen::<T, I>::insert(&who, ());
+			Self::deposit_event(Event::<T, I>::AccountFrozen { account: who });
+			Ok(())
+		}
+
+		/// Thaws `who`, frozen by `freeze_account`.
+		#[pallet::call_index(30)]
+		#[pallet::weight(T::WeightInfo::thaw_account())]
+		pub fn thaw_account(origin: OriginFor<T>, who: AccountIdLookupOf<T>) -> DispatchResult {
+			T::FreezeOrigin::ensure_origin(origin)?;
+			let who = T::Lookup::lookup(who)?;
+			Frozen::<T, I>::remove(&who);
+			Self::deposit_event(Event::<T, I>::AccountThawed { account: who });
+			Ok(())
+		}
+
+		/// Adds `who` to the allowlist.
+		#[pallet::call_index(31)]
+		#[pallet::weight(T::WeightInfo::add_to_allowlist())]
+		pub fn add_to_allowlist(origin: OriginFor<T>, who: AccountIdLookupOf<T>) -> DispatchResult {
+			T::AdminOrigin::ensure_origin(origin)?;
+			Self::_add_to_allowlist(T::Lookup::lookup(who)?);
+			Ok(())
+		}
+
+		/// Removes `who` from the allowlist.
+		#[pallet::call_index(32)]
+		#[pallet::weight(T::WeightInfo::remove_from_allowlist())]
+		pub fn remove_from_allowlist(
+			origin: OriginFor<T>,
+			who: AccountIdLookupOf<T>,
+		) -> DispatchResult {
+			T::AdminOrigin::ensure_origin(origin)?;
+			Self::_remove_from_allowlist(T::Lookup::lookup(who)?);
+			Ok(())
+		}
+
+		/// Adds every account of `accounts` to the allowlist.
+		#[pallet::call_index(33)]
+		#[pallet::weight(T::WeightInfo::batch_add_to_allowlist(accounts.len() as u32))]
+		pub fn batch_add_to_allowlist(
+			origin: OriginFor<T>,
+			accounts: Vec<AccountIdLookupOf<T>>,
+		) -> DispatchResult {
+			T::AdminOrigin::ensure_origin(origin)?;
+			ensure!(
+				accounts.len() <= T::MaxBatchSize::get() as usize,
+				Error::<T, I>::TooManyAccounts
+			);
+			for who in accounts {
+				Self::_add_to_allowlist(T::Lookup::lookup(who)?);
+			}
+			Ok(())
+		}
+
+		/// Removes every account of `accounts` from the allowlist.
+		#[pallet::call_index(34)]
+		#[pallet::weight(T::WeightInfo::batch_remove_from_allowlist(accounts.len() as u32))]
+		pub fn batch_remove_from_allowlist(
+			origin: OriginFor<T>,
+			accounts: Vec<AccountIdLookupOf<T>>,
+		) -> DispatchResult {
+			T::AdminOrigin::ensure_origin(origin)?;
+			ensure!(
+				accounts.len() <= T::MaxBatchSize::get() as usize,
+				Error::<T, I>::TooManyAccounts
+			);
+			for who in accounts {
+				Self::_remove_from_allowlist(T::Lookup::lookup(who)?);
+			}
+			Ok(())
+		}
+
+		/// Enables or disables the allowlist. While it is enabled, only allowlisted accounts can
+		/// receive tokens and transfer them.
+		#[pallet::call_index(35)]
+		#[pallet::weight(T::WeightInfo::set_allowlist_enabled())]
+		pub fn set_allowlist_enabled(origin: OriginFor<T>, enabled: bool) -> DispatchResult {
+			T::AdminOrigin::ensure_origin(origin)?;
+			AllowlistEnabled::<T, I>::put(enabled);
+			Self::deposit_event(Event::<T, I>::AllowlistEnabledSet { enabled });
+			Ok(())
+		}
+
+		/// Sets the fee charged on `transfer`, `transfer_keep_alive`, `transfer_from` and
+		/// `batch_transfer` to `bps` basis points of the amount, at most `Config::MaxFeeBps`.
+		#[pallet::call_index(36)]
+		#[pallet::weight(T::WeightInfo::set_transfer_fee())]
+		pub fn set_transfer_fee(origin: OriginFor<T>, bps: u16) -> DispatchResult {
+			T::AdminOrigin::ensure_origin(origin)?;
+			ensure!(bps <= T::MaxFeeBps::get(), Error::<T, I>::TransferFeeTooHigh);
+			TransferFeeBps::<T, I>::put(bps);
+			Self::deposit_event(Event::<T, I>::TransferFeeSet { bps });
+			Ok(())
+		}
+
+		/// Exempts `who` from the transfer fee, on what it sends and receives, or lifts the
+		/// exemption.
+		#[pallet::call_index(37)]
+		#[pallet::weight(T::WeightInfo::set_transfer_fee_exempt())]
+		pub fn set_transfer_fee_exempt(
+			origin: OriginFor<T>,
+			who: AccountIdLookupOf<T>,
+			exempt: bool,
+		) -> DispatchResult {
+			T::AdminOrigin::ensure_origin(origin)?;
+			let who = T::Lookup::lookup(who)?;
+			if exempt {
+				TransferFeeExempt::<T, I>::insert(&who, ());
+			} else {
+				TransferFeeExempt::<T, I>::remove(&who);
+			}
+			Self::deposit_event(Event::<T, I>::TransferFeeExemptionSet { account: who, exempt });
+			Ok(())
+		}
 	}
 }
 
 //internal functions
 //will keep naming according too erc20 in solidity, which is actually wrong
-impl<T: Config> Pallet<T> {
-	pub fn _transfer(from: T::AccountId, to: T::AccountId, value: u64) -> Result<(), Error<T>> {
-		let new_balance_from = Balances::<T>::get(from.clone())
-			.checked_sub(value)
-			.ok_or(Error::<T>::ERC20InsufficientBalance)?;
-		let new_balance_to = Balances::<T>::get(to.clone())
-			.checked_add(value)
-			.ok_or(Error::<T>::StorageOverflow)?;
+impl<T: Config<I>, I: 'static> Pallet<T, I> {
+	/// Moves `value` tokens from `from` to `to`. `from` is removed if its balance falls below
+	/// `Config::MinimumBalance`, unless `keep_alive` is set, in which case the transfer fails.
+	pub fn _transfer(
+		from: T::AccountId,
+		to: T::AccountId,
+		value: T::Balance,
+		keep_alive: bool,
+	) -> Result<(), Error<T, I>> {
+		Self::ensure_not_paused(Operation::Transfer)?;
+		Self::ensure_not_frozen(&from)?;
+		Self::ensure_not_frozen(&to)?;
+		Self::ensure_allowlisted(&from)?;
+		Self::ensure_allowlisted(&to)?;
+		let new_balance_from = Balances::<T, I>::get(from.clone())
+			.checked_sub(&value)
+			.ok_or(Error::<T, I>::ERC20InsufficientBalance)?;
 
-		Balances::<T>::insert(from.clone(), new_balance_from);
-		Balances::<T>::insert(to.clone(), new_balance_to);
-		Self::deposit_event(Event::<T>::Transfer { from, to, value });
+		let mut dust = Zero::zero();
+		if from != to {
+			Self::ensure_unlocked(&from, new_balance_from)?;
+			if keep_alive && Self::below_minimum(&from, new_balance_from) {
+				return Err(Error::<T, I>::WouldReapAccount)
+			}
+			let new_balance_to = Balances::<T, I>::get(to.clone())
+				.checked_add(&value)
+				.ok_or(Error::<T, I>::StorageOverflow)?;
+			if Self::below_minimum(&to, new_balance_to) {
+				return Err(Error::<T, I>::BelowMinimumBalance)
+			}
+
+			dust = Self::write_or_reap(&from, new_balance_from);
+			Self::write_or_reap(&to, new_balance_to);
+		}
+		Self::deposit_event(Event::<T, I>::Transfer { from: from.clone(), to, value });
+		Self::lose_dust(from, dust);
+		Ok(())
+	}
+
+	/// Moves `value` tokens from `from` to `to` like `_transfer`, crediting
+	/// `Config::TransferFeeAccount` with the transfer fee deducted from `value`.
+	pub fn transfer_with_fee(
+		from: T::AccountId,
+		to: T::AccountId,
+		value: T::Balance,
+		keep_alive: bool,
+	) -> Result<(), Error<T, I>> {
+		let fee = Self::transfer_fee(&from, &to, value);
+		if fee.is_zero() {
+			return Self::_transfer(from, to, value, keep_alive)
+		}
+		// The fee is taken first, so that `from` is not reaped before paying it.
+		Self::_transfer(from.clone(), T::TransferFeeAccount::get(), fee, keep_alive)?;
+		Self::_transfer(from.clone(), to.clone(), value - fee, keep_alive)?;
+		Self::deposit_event(Event::<T, I>::FeeCharged { from, to, fee });
 		Ok(())
 	}
 
+	/// Fee charged on a transfer of `value` tokens from `from` to `to`, none if either is
+	/// exempt, `from` is `Config::TransferFeeAccount` or `from` is `to`.
+	///
+	/// A fee that would leave `Config::TransferFeeAccount` below `Config::MinimumBalance` is not
+	/// charged, so that small transfers do not fail before the fee account exists.
+	pub fn transfer_fee(from: &T::AccountId, to: &T::AccountId, value: T::Balance) -> T::Balance {
+		let bps = TransferFeeBps::<T, I>::get();
+		let fee_account = T::TransferFeeAccount::get();
+		if bps.is_zero() ||
+			from == to ||
+			*from == fee_account ||
+			TransferFeeExempt::<T, I>::contains_key(from) ||
+			TransferFeeExempt::<T, I>::contains_key(to)
+		{
+			return Zero::zero()
+		}
+		let fee = Permill::from_parts(u32::from(bps) * 100) * value;
+		if Self::below_minimum(
+			&fee_account,
+			Balances::<T, I>::get(&fee_account).saturating_add(fee),
+		) {
+			return Zero::zero()
+		}
+		fee
+	}
+
 	pub fn _spend_allowance(
 		from: T::AccountId,
 		to: T::AccountId,
-		value: u64,
-	) -> Result<(), Error<T>> {
-		let current_allowance = Allowances::<T>::get(from.clone(), to.clone());
+		value: T::Balance,
+	) -> DispatchResult {
+		// The owner is checked by `_transfer` and `_burn` when the tokens are moved.
+		Self::ensure_not_frozen(&to)?;
+		let current_allowance = Allowances::<T, I>::get(from.clone(), to.clone());
 
-		if current_allowance != u64::MAX {
+		if current_allowance != T::Balance::max_value() {
 			let result = current_allowance
-				.checked_sub(value)
-				.ok_or(Error::<T>::ERC20InsufficientAllowance)?;
-			Self::_approve(from, to, result);
+				.checked_sub(&value)
+				.ok_or(Error::<T, I>::ERC20InsufficientAllowance)?;
+			Self::_approve(from, to, result)?;
+		}
+		Ok(())
+	}
+
+	/// Sets the allowance of `spender` over the tokens of `owner`. A new allowance entry reserves
+	/// `Config::AllowanceDeposit` from `owner`, a zero allowance removes the entry and releases
+	/// its deposit.
+	pub fn _approve(
+		owner: T::AccountId,
+		spender: T::AccountId,
+		value: T::Balance,
+	) -> DispatchResult {
+		let depositor = owner.clone();
+		Self::approve_with_depositor(owner, spender, value, &depositor)
+	}
+
+	/// Sets the allowance of `spender` over the tokens of `owner` like `_approve`, reserving the
+	/// deposit of a new allowance entry from `depositor`. The deposit is released to the account
+	/// that paid it.
+	pub fn approve_with_depositor(
+		owner: T::AccountId,
+		spender: T::AccountId,
+		value: T::Balance,
+		depositor: &T::AccountId,
+	) -> DispatchResult {
+		if value.is_zero() {
+			Allowances::<T, I>::remove(&owner, &spender);
+			if let Some((depositor, deposit)) = AllowanceDeposits::<T, I>::take(&owner, &spender) {
+				T::Currency::unreserve(&depositor, deposit);
+			}
+		} else {
+			if !Allowances::<T, I>::contains_key(&owner, &spender) {
+				let deposit = T::AllowanceDeposit::get();
+				if !deposit.is_zero() {
+					T::Currency::reserve(depositor, deposit)?;
+					AllowanceDeposits::<T, I>::insert(
+						&owner,
+						&spender,
+						(depositor.clone(), deposit),
+					);
+				}
+			}
+			Allowances::<T, I>::insert(&owner, &spender, value);
+		}
+		Self::deposit_event(Event::<T, I>::Approval { owner, spender, value });
+		Ok(())
+	}
+
+	pub fn _mint(to: T::AccountId, value: T::Balance) -> Result<(), Error<T, I>> {
+		Self::ensure_not_paused(Operation::Mint)?;
+		Self::ensure_not_frozen(&to)?;
+		Self::ensure_allowlisted(&to)?;
+		let new_supply = TotalSupply::<T, I>::get()
+			.checked_add(&value)
+			.ok_or(Error::<T, I>::StorageOverflow)?;
+		if new_supply > T::MaxSupply::get() {
+			return Err(Error::<T, I>::SupplyCapExceeded)
+		}
+		let new_balance = Balances::<T, I>::get(to.clone())
+			.checked_add(&value)
+			.ok_or(Error::<T, I>::StorageOverflow)?;
+		if Self::below_minimum(&to, new_balance) {
+			return Err(Error::<T, I>::BelowMinimumBalance)
+		}
+		Self::write_total_supply(new_supply);
+		Self::write_or_reap(&to, new_balance);
+		Ok(())
+	}
+
+	pub fn _burn(to: T::AccountId, value: T::Balance) -> Result<(), Error<T, I>> {
+		Self::ensure_not_paused(Operation::Burn)?;
+		Self::ensure_not_frozen(&to)?;
+		let new_balance = Balances::<T, I>::get(to.clone())
+			.checked_sub(&value)
+			.ok_or(Error::<T, I>::ERC20InsufficientBalance)?;
+		Self::ensure_unlocked(&to, new_balance)?;
+		let new_supply = TotalSupply::<T, I>::get()
+			.checked_sub(&value)
+			.ok_or(Error::<T, I>::StorageOverflow)?;
+		Self::write_total_supply(new_supply);
+		let dust = Self::write_or_reap(&to, new_balance);
+		Self::deposit_event(Event::<T, I>::Burned { from: to.clone(), value });
+		Self::lose_dust(to, dust);
+		Ok(())
+	}
+
+	/// Writes the free balance of `who`, removing the account if the balance is zero or the
+	/// account is left below `Config::MinimumBalance`. Returns the dust left by a removed account.
+	///
+	/// The vesting schedules of a removed account go with it, the tokens they locked being dust.
+	pub(crate) fn write_or_reap(who: &T::AccountId, balance: T::Balance) -> T::Balance {
+		let old_balance = Balances::<T, I>::get(who);
+		let (new_balance, dust) = if balance.is_zero() || Self::below_minimum(who, balance) {
+			Balances::<T, I>::remove(who);
+			Vesting::<T, I>::remove(who);
+			Locked::<T, I>::remove(who);
+			(Zero::zero(), balance)
+		} else {
+			Balances::<T, I>::insert(who, balance);
+			(balance, Zero::zero())
+		};
+
+		let reserved = Reserved::<T, I>::get(who);
+		Self::on_balance_changed(
+			who,
+			old_balance.saturating_add(reserved),
+			new_balance.saturating_add(reserved),
+		);
+		dust
+	}
+
+	/// Checkpoints the balance of `who` for the current snapshot and moves the voting power of its
+	/// delegate once its balance, reserved balance included, changed from `old` to `new`.
+	fn on_balance_changed(who: &T::AccountId, old: T::Balance, new: T::Balance) {
+		let current = CurrentSnapshotId::<T, I>::get();
+		let count = NumAccountSnapshots::<T, I>::get(who);
+		let last = count.checked_sub(1).and_then(|index| AccountSnapshots::<T, I>::get(who, index));
+		if last.map_or(0, |last| last.id) < current {
+			let checkpoint = Checkpoint { id: current, value: old };
+			AccountSnapshots::<T, I>::insert(who, count, checkpoint);
+			NumAccountSnapshots::<T, I>::insert(who, count.saturating_add(1));
+		}
+
+		let delegate = Delegates::<T, I>::get(who);
+		if new > old {
+			Self::move_voting_power(None, delegate.as_ref(), new - old);
+		} else {
+			Self::move_voting_power(delegate.as_ref(), None, old - new);
+		}
+	}
+
+	/// Ensures the free balance of `who` can be lowered to `free` without spending tokens locked
+	/// by vesting.
+	fn ensure_unlocked(who: &T::AccountId, free: T::Balance) -> Result<(), Error<T, I>> {
+		if free < Locked::<T, I>::get(who) {
+			return Err(Error::<T, I>::BalanceLocked)
+		}
+		Ok(())
+	}
+
+	/// Adds `schedule` to the vesting schedules of `who` and locks its tokens.
+	fn add_vesting_schedule(
+		who: &T::AccountId,
+		schedule: VestingSchedule<T::Balance, BlockNumberFor<T>>,
+	) -> Result<(), Error<T, I>> {
+		if schedule.locked.is_zero() || schedule.per_block.is_zero() {
+			return Err(Error::<T, I>::InvalidVestingSchedule)
+		}
+		Vesting::<T, I>::try_mutate(who, |schedules| schedules.try_push(schedule))
+			.map_err(|_| Error::<T, I>::TooManyVestingSchedules)?;
+		Self::update_vesting_lock(who);
+		Self::deposit_event(Event::<T, I>::VestingScheduleAdded { account: who.clone(), schedule });
+		Ok(())
+	}
+
+	/// Locks the tokens of `who` its vesting schedules have not unlocked yet and drops the
+	/// finished schedules. Returns the locked amount.
+	fn update_vesting_lock(who: &T::AccountId) -> T::Balance {
+		let now = frame_system::Pallet::<T>::block_number();
+		let mut schedules = Vesting::<T, I>::get(who);
+		let mut locked = T::Balance::zero();
+		schedules.retain(|schedule| {
+			let unvested = schedule.locked_at::<T::BlockNumberToBalance>(now);
+			locked = locked.saturating_add(unvested);
+			!unvested.is_zero()
+		});
+
+		if schedules.is_empty() {
+			Vesting::<T, I>::remove(who);
+			Locked::<T, I>::remove(who);
+		} else {
+			Vesting::<T, I>::insert(who, schedules);
+			Locked::<T, I>::insert(who, locked);
+		}
+		locked
+	}
+
+	/// Whether `who` is below `Config::MinimumBalance` with a free balance of `free`, its reserved
+	/// balance included.
+	fn below_minimum(who: &T::AccountId, free: T::Balance) -> bool {
+		free.saturating_add(Reserved::<T, I>::get(who)) < T::MinimumBalance::get()
+	}
+
+	/// Writes the reserved balance of `who`, removing the entry once nothing is reserved.
+	pub(crate) fn write_reserved(who: &T::AccountId, reserved: T::Balance) {
+		let old_reserved = Reserved::<T, I>::get(who);
+		if reserved.is_zero() {
+			Reserved::<T, I>::remove(who);
+		} else {
+			Reserved::<T, I>::insert(who, reserved);
+		}
+
+		let free = Balances::<T, I>::get(who);
+		Self::on_balance_changed(
+			who,
+			free.saturating_add(old_reserved),
+			free.saturating_add(reserved),
+		);
+	}
+
+	/// Free and reserved balance of `who`.
+	pub fn total_balance_of(who: &T::AccountId) -> T::Balance {
+		Balances::<T, I>::get(who).saturating_add(Reserved::<T, I>::get(who))
+	}
+
+	/// Balance of `who` on hold for any reason.
+	pub fn total_on_hold(who: &T::AccountId) -> T::Balance {
+		Holds::<T, I>::iter_prefix_values(who)
+			.fold(Zero::zero(), |total: T::Balance, held| total.saturating_add(held))
+	}
+
+	/// Reserved balance of `who` that is not on hold.
+	fn unheld_reserved(who: &T::AccountId) -> T::Balance {
+		Reserved::<T, I>::get(who).saturating_sub(Self::total_on_hold(who))
+	}
+
+	/// Moves `value` of the free balance of `who` to its reserved balance, where it cannot be
+	/// transferred until it is unreserved.
+	pub fn reserve(who: &T::AccountId, value: T::Balance) -> DispatchResult {
+		if value.is_zero() {
+			return Ok(())
 		}
+		Self::ensure_not_paused(Operation::Transfer)?;
+		Self::ensure_not_frozen(who)?;
+		let free = Balances::<T, I>::get(who)
+			.checked_sub(&value)
+			.ok_or(Error::<T, I>::ERC20InsufficientBalance)?;
+		Self::ensure_unlocked(who, free)?;
+		let reserved = Reserved::<T, I>::get(who)
+			.checked_add(&value)
+			.ok_or(Error::<T, I>::StorageOverflow)?;
+		Self::write_reserved(who, reserved);
+		Self::write_or_reap(who, free);
+		Self::deposit_event(Event::<T, I>::Reserved { who: who.clone(), value });
 		Ok(())
 	}
 
-	pub fn _approve(owner: T::AccountId, spender: T::AccountId, value: u64) {
-		Allowances::<T>::insert(owner.clone(), spender.clone(), value);
-		Self::deposit_event(Event::<T>::Approval { owner, spender, value });
+	/// Moves up to `value` of the reserved balance of `who` back to its free balance. The balance
+	/// on hold is not released. Returns the amount that could not be unreserved.
+	///
+	/// Frozen accounts can be unreserved, as the tokens do not leave the account.
+	pub fn unreserve(who: &T::AccountId, value: T::Balance) -> T::Balance {
+		let actual = value.min(Self::unheld_reserved(who));
+		if actual.is_zero() {
+			return value
+		}
+		Self::write_reserved(who, Reserved::<T, I>::get(who).saturating_sub(actual));
+		Self::write_or_reap(who, Balances::<T, I>::get(who).saturating_add(actual));
+		Self::deposit_event(Event::<T, I>::Unreserved { who: who.clone(), value: actual });
+		value.saturating_sub(actual)
+	}
+
+	/// Moves up to `value` of the reserved balance of `slashed`, the balance on hold excluded, to
+	/// the free or reserved balance of `beneficiary` given by `status`. Returns the amount that
+	/// could not be moved.
+	pub fn repatriate_reserved(
+		slashed: &T::AccountId,
+		beneficiary: &T::AccountId,
+		value: T::Balance,
+		status: BalanceStatus,
+	) -> Result<T::Balance, DispatchError> {
+		Self::ensure_not_paused(Operation::Transfer)?;
+		Self::ensure_not_frozen(slashed)?;
+		Self::ensure_not_frozen(beneficiary)?;
+		Self::ensure_allowlisted(beneficiary)?;
+		let actual = value.min(Self::unheld_reserved(slashed));
+		if slashed == beneficiary {
+			return Ok(match status {
+				BalanceStatus::Free => Self::unreserve(slashed, value),
+				BalanceStatus::Reserved => value.saturating_sub(actual),
+			})
+		}
+		if actual.is_zero() {
+			return Ok(value)
+		}
+
+		let free = Balances::<T, I>::get(beneficiary);
+		let reserved = Reserved::<T, I>::get(beneficiary);
+		let (free, reserved) = match status {
+			BalanceStatus::Free =>
+				(free.checked_add(&actual).ok_or(Error::<T, I>::StorageOverflow)?, reserved),
+			BalanceStatus::Reserved =>
+				(free, reserved.checked_add(&actual).ok_or(Error::<T, I>::StorageOverflow)?),
+		};
+		if free.saturating_add(reserved) < T::MinimumBalance::get() {
+			return Err(Error::<T, I>::BelowMinimumBalance.into())
+		}
+		Self::write_reserved(beneficiary, reserved);
+		Self::write_or_reap(beneficiary, free);
+
+		Self::write_reserved(slashed, Reserved::<T, I>::get(slashed).saturating_sub(actual));
+		// The free balance of `slashed` may be left as dust once nothing is reserved.
+		let dust = Self::write_or_reap(slashed, Balances::<T, I>::get(slashed));
+		Self::deposit_event(Event::<T, I>::ReserveRepatriated {
+			from: slashed.clone(),
+			to: beneficiary.clone(),
+			value: actual,
+			destination_status: status,
+		});
+		Self::lose_dust(slashed.clone(), dust);
+		Ok(value.saturating_sub(actual))
+	}
+
+	/// Writes the total supply, checkpointing the previous one for the current snapshot first and
+	/// the new one for the current block.
+	pub(crate) fn write_total_supply(total_supply: T::Balance) {
+		let current = CurrentSnapshotId::<T, I>::get();
+		let count = NumTotalSupplySnapshots::<T, I>::get();
+		let last = count.checked_sub(1).and_then(TotalSupplySnapshots::<T, I>::get);
+		if last.map_or(0, |last| last.id) < current {
+			let checkpoint = Checkpoint { id: current, value: TotalSupply::<T, I>::get() };
+			TotalSupplySnapshots::<T, I>::insert(count, checkpoint);
+			NumTotalSupplySnapshots::<T, I>::put(count.saturating_add(1));
+		}
+		TotalSupply::<T, I>::put(total_supply);
+		Self::push_total_supply_checkpoint(total_supply);
+	}
+
+	/// Balance of `who`, reserved balance included, when snapshot `id` was taken.
+	pub fn balance_of_at(who: &T::AccountId, id: SnapshotId) -> Result<T::Balance, Error<T, I>> {
+		Self::ensure_snapshot_exists(id)?;
+		Ok(Self::value_at(
+			id,
+			NumAccountSnapshots::<T, I>::get(who),
+			Self::total_balance_of(who),
+			|index| AccountSnapshots::<T, I>::get(who, index),
+		))
+	}
+
+	/// Total supply when snapshot `id` was taken.
+	pub fn total_supply_at(id: SnapshotId) -> Result<T::Balance, Error<T, I>> {
+		Self::ensure_snapshot_exists(id)?;
+		Ok(Self::value_at(
+			id,
+			NumTotalSupplySnapshots::<T, I>::get(),
+			TotalSupply::<T, I>::get(),
+			TotalSupplySnapshots::<T, I>::get,
+		))
+	}
+
+	fn ensure_snapshot_exists(id: SnapshotId) -> Result<(), Error<T, I>> {
+		if id.is_zero() || id > CurrentSnapshotId::<T, I>::get() {
+			return Err(Error::<T, I>::NonexistentSnapshot)
+		}
+		Ok(())
+	}
+
+	/// Value at snapshot `id`, held by the earliest of the `count` checkpoints taken at or after
+	/// `id`, found by binary search, or `current` if the value did not change since.
+	fn value_at(
+		id: SnapshotId,
+		count: u32,
+		current: T::Balance,
+		checkpoint: impl Fn(u32) -> Option<Checkpoint<T::Balance>>,
+	) -> T::Balance {
+		let (mut low, mut high) = (0, count);
+		while low < high {
+			let mid = low + (high - low) / 2;
+			match checkpoint(mid) {
+				Some(found) if found.id >= id => high = mid,
+				_ => low = mid + 1,
+			}
+		}
+		checkpoint(high).map_or(current, |found| found.value)
+	}
+
+	/// Makes `delegatee` the delegate of `delegator`, moving the voting power of its balance,
+	/// reserved balance included.
+	pub(crate) fn _delegate(delegator: T::AccountId, delegatee: T::AccountId) {
+		let from_delegate = Delegates::<T, I>::get(&delegator);
+		Delegates::<T, I>::insert(&delegator, &delegatee);
+		Self::deposit_event(Event::<T, I>::DelegateChanged {
+			delegator: delegator.clone(),
+			from_delegate: from_delegate.clone(),
+			to_delegate: delegatee.clone(),
+		});
+		Self::move_voting_power(
+			from_delegate.as_ref(),
+			Some(&delegatee),
+			Self::total_balance_of(&delegator),
+		);
+	}
+
+	/// Moves `amount` of voting power from the delegate `from` to the delegate `to`, `None`
+	/// standing for tokens minted, burned or held by an account without a delegate.
+	fn move_voting_power(
+		from: Option<&T::AccountId>,
+		to: Option<&T::AccountId>,
+		amount: T::Balance,
+	) {
+		if from == to || amount.is_zero() {
+			return
+		}
+		if let Some(delegate) = from {
+			Self::update_votes(delegate, |votes| votes.saturating_sub(amount));
+		}
+		if let Some(delegate) = to {
+			Self::update_votes(delegate, |votes| votes.saturating_add(amount));
+		}
+	}
+
+	/// Checkpoints the voting power of `delegate` updated by `f` for the current block.
+	fn update_votes(delegate: &T::AccountId, f: impl FnOnce(T::Balance) -> T::Balance) {
+		let now = frame_system::Pallet::<T>::block_number();
+		let count = NumVoteCheckpoints::<T, I>::get(delegate);
+		let last = count
+			.checked_sub(1)
+			.and_then(|index| VoteCheckpoints::<T, I>::get(delegate, index));
+		let previous_votes = last.map_or_else(Zero::zero, |last| last.votes);
+		let checkpoint = VoteCheckpoint { from_block: now, votes: f(previous_votes) };
+		match last {
+			Some(last) if last.from_block == now =>
+				VoteCheckpoints::<T, I>::insert(delegate, count - 1, checkpoint),
+			_ => {
+				VoteCheckpoints::<T, I>::insert(delegate, count, checkpoint);
+				NumVoteCheckpoints::<T, I>::insert(delegate, count.saturating_add(1));
+			},
+		}
+		Self::deposit_event(Event::<T, I>::DelegateVotesChanged {
+			delegate: delegate.clone(),
+			previous_votes,
+			new_votes: checkpoint.votes,
+		});
+	}
+
+	/// Records `total_supply` as the total supply from the current block on.
+	pub(crate) fn push_total_supply_checkpoint(total_supply: T::Balance) {
+		let now = frame_system::Pallet::<T>::block_number();
+		let count = NumTotalSupplyCheckpoints::<T, I>::get();
+		let last = count.checked_sub(1).and_then(TotalSupplyCheckpoints::<T, I>::get);
+		let checkpoint = VoteCheckpoint { from_block: now, votes: total_supply };
+		match last {
+			Some(last) if last.from_block == now =>
+				TotalSupplyCheckpoints::<T, I>::insert(count - 1, checkpoint),
+			_ => {
+				TotalSupplyCheckpoints::<T, I>::insert(count, checkpoint);
+				NumTotalSupplyCheckpoints::<T, I>::put(count.saturating_add(1));
+			},
+		}
+	}
+
+	/// Current voting power of `who`.
+	pub fn get_votes(who: &T::AccountId) -> T::Balance {
+		NumVoteCheckpoints::<T, I>::get(who)
+			.checked_sub(1)
+			.and_then(|index| VoteCheckpoints::<T, I>::get(who, index))
+			.map_or_else(Zero::zero, |checkpoint| checkpoint.votes)
+	}
+
+	/// Voting power of `who` at the end of the past block `block`.
+	pub fn get_past_votes(
+		who: &T::AccountId,
+		block: BlockNumberFor<T>,
+	) -> Result<T::Balance, Error<T, I>> {
+		Self::ensure_past_block(block)?;
+		Ok(Self::checkpoint_lookup(block, NumVoteCheckpoints::<T, I>::get(who), |index| {
+			VoteCheckpoints::<T, I>::get(who, index)
+		}))
+	}
+
+	/// Total supply at the end of the past block `block`, zero before the first total supply
+	/// checkpoint.
+	pub fn get_past_total_supply(block: BlockNumberFor<T>) -> Result<T::Balance, Error<T, I>> {
+		Self::ensure_past_block(block)?;
+		Ok(Self::checkpoint_lookup(
+			block,
+			NumTotalSupplyCheckpoints::<T, I>::get(),
+			TotalSupplyCheckpoints::<T, I>::get,
+		))
 	}
 
-	pub fn _mint(to: T::AccountId, value: u64) -> Result<(), Error<T>> {
-		TotalSupply::<T>::put(value.clone());
-		let new_balance = Balances::<T>::get(to.clone())
-			.checked_add(value)
-			.ok_or(Error::<T>::StorageOverflow)?;
-		Balances::<T>::insert(to, new_balance);
+	fn ensure_past_block(block: BlockNumberFor<T>) -> Result<(), Error<T, I>> {
+		if block >= frame_system::Pallet::<T>::block_number() {
+			return Err(Error::<T, I>::FutureLookup)
+		}
 		Ok(())
 	}
 
-	pub fn _burn(to: T::AccountId, value: u64) -> Result<(), Error<T>> {
-		TotalSupply::<T>::put(value.clone());
-		let new_balance = Balances::<T>::get(to.clone())
-			.checked_sub(value)
-			.ok_or(Error::<T>::ERC20InsufficientBalance)?;
-		Balances::<T>::insert(to, new_balance);
+	/// Value of the latest of the `count` checkpoints starting at or before `block`, found by
+	/// binary search, or zero if there is none.
+	fn checkpoint_lookup(
+		block: BlockNumberFor<T>,
+		count: u32,
+		checkpoint: impl Fn(u32) -> Option<VoteCheckpoint<T::Balance, BlockNumberFor<T>>>,
+	) -> T::Balance {
+		let (mut low, mut high) = (0, count);
+		while low < high {
+			let mid = low + (high - low) / 2;
+			match checkpoint(mid) {
+				Some(found) if found.from_block > block => high = mid,
+				_ => low = mid + 1,
+			}
+		}
+		high.checked_sub(1)
+			.and_then(checkpoint)
+			.map_or_else(Zero::zero, |found| found.votes)
+	}
+
+	/// Burns the `dust` left by the removed account `who`.
+	fn lose_dust(who: T::AccountId, dust: T::Balance) {
+		if dust.is_zero() {
+			return
+		}
+		Self::write_total_supply(TotalSupply::<T, I>::get().saturating_sub(dust));
+		Self::deposit_event(Event::<T, I>::DustLost { account: who, amount: dust });
+	}
+
+	/// Returns the role administering `role`.
+	pub fn role_admin(role: Role) -> Role {
+		RoleAdmins::<T, I>::get(role).unwrap_or(Role::Admin)
+	}
+
+	/// Whether `who` holds `role`.
+	pub fn has_role(role: Role, who: &T::AccountId) -> bool {
+		Roles::<T, I>::contains_key(role, who)
+	}
+
+	/// All accounts holding `role`.
+	pub fn role_members(role: Role) -> Vec<T::AccountId> {
+		Roles::<T, I>::iter_key_prefix(role).collect()
+	}
+
+	pub fn _check_role(role: Role, who: &T::AccountId) -> Result<(), Error<T, I>> {
+		if !Self::has_role(role, who) {
+			return Err(Error::<T, I>::AccessControl(role))
+		}
+		Ok(())
+	}
+
+	pub fn _grant_role(
+		role: Role,
+		who: T::AccountId,
+		sender: Option<T::AccountId>,
+	) -> Result<(), Error<T, I>> {
+		if Self::insert_role_member(role, &who)? {
+			Self::deposit_event(Event::<T, I>::RoleGranted { role, account: who, sender });
+		}
+		Ok(())
+	}
+
+	pub fn _revoke_role(role: Role, who: T::AccountId, sender: Option<T::AccountId>) {
+		if Roles::<T, I>::take(role, &who).is_some() {
+			RoleMemberCount::<T, I>::mutate(role, |count| *count = count.saturating_sub(1));
+			Self::deposit_event(Event::<T, I>::RoleRevoked { role, account: who, sender });
+		}
+	}
+
+	/// Adds `who` to `role`, returns `false` if it already held the role.
+	fn insert_role_member(role: Role, who: &T::AccountId) -> Result<bool, Error<T, I>> {
+		if Self::has_role(role, who) {
+			return Ok(false)
+		}
+		let count = RoleMemberCount::<T, I>::get(role);
+		if count >= T::MaxRoleMembers::get() {
+			return Err(Error::<T, I>::TooManyRoleMembers)
+		}
+		RoleMemberCount::<T, I>::insert(role, count + 1);
+		Roles::<T, I>::insert(role, who, ());
+		Ok(true)
+	}
+
+	/// Ensures `origin` is `Config::RoleAdminOrigin` or signed by a holder of the admin role of
+	/// `role`. Returns the signer, if any.
+	fn ensure_role_admin(
+		origin: OriginFor<T>,
+		role: Role,
+	) -> Result<Option<T::AccountId>, DispatchError> {
+		match T::RoleAdminOrigin::try_origin(origin) {
+			Ok(_) => Ok(None),
+			Err(origin) => {
+				let sender = ensure_signed(origin)?;
+				Self::_check_role(Self::role_admin(role), &sender)?;
+				Ok(Some(sender))
+			},
+		}
+	}
+
+	/// Payload `owner` has to sign to `permit` `spender` to spend `value` until `deadline`, for
+	/// the current nonce of `owner`.
+	pub fn permit_payload(
+		owner: T::AccountId,
+		spender: T::AccountId,
+		value: T::Balance,
+		deadline: BlockNumberFor<T>,
+	) -> PermitPayload<T::Hash, T::AccountId, T::Balance, BlockNumberFor<T>> {
+		PermitPayload {
+			genesis_hash: frame_system::Pallet::<T>::block_hash(BlockNumberFor::<T>::zero()),
+			pallet_index: <Self as PalletInfoAccess>::index() as u8,
+			nonce: PermitNonces::<T, I>::get(&owner),
+			owner,
+			spender,
+			value,
+			deadline,
+		}
+	}
+
+	/// Token amount charged for a transaction fee of `fee` in the native currency.
+	pub fn convert_fee(fee: T::Balance) -> T::Balance
+	where
+		T::Balance: FixedPointOperand,
+	{
+		FeeRate::<T, I>::get().saturating_mul_int(fee)
+	}
+
+	/// Maximum amount `flash_mint` can lend.
+	pub fn max_flash_mint() -> T::Balance {
+		T::MaxFlashMint::get().min(Self::remaining_mintable())
+	}
+
+	/// Fee `flash_mint` charges for lending `amount`, none if it would leave
+	/// `Config::FlashMintFeeReceiver` below `Config::MinimumBalance`, as for `transfer_fee`.
+	pub fn flash_mint_fee(amount: T::Balance) -> T::Balance {
+		let fee = T::FlashMintFee::get() * amount;
+		let receiver = T::FlashMintFeeReceiver::get();
+		if Self::below_minimum(&receiver, Balances::<T, I>::get(&receiver).saturating_add(fee)) {
+			return Zero::zero()
+		}
+		fee
+	}
+
+	/// Reports a borrower short of the tokens to repay a flash mint and its fee as
+	/// `FlashMintNotRepaid`, leaving the other errors as they are.
+	fn not_repaid(error: Error<T, I>) -> Error<T, I> {
+		match error {
+			Error::<T, I>::ERC20InsufficientBalance | Error::<T, I>::BalanceLocked =>
+				Error::<T, I>::FlashMintNotRepaid,
+			error => error,
+		}
+	}
+
+	/// Amount that can still be minted before reaching `Config::MaxSupply`.
+	pub fn remaining_mintable() -> T::Balance {
+		T::MaxSupply::get().saturating_sub(TotalSupply::<T, I>::get())
+	}
+
+	/// Takes `value` from the mint quota of `minter`, starting a new period of its rate limit if
+	/// the current one is over. Minters without a quota are not limited.
+	pub fn use_mint_quota(minter: &T::AccountId, value: T::Balance) -> Result<(), Error<T, I>> {
+		MintQuotas::<T, I>::try_mutate_exists(minter, |maybe_quota| {
+			let Some(quota) = maybe_quota else { return Ok(()) };
+			quota.remaining =
+				quota.remaining.checked_sub(&value).ok_or(Error::<T, I>::MintQuotaExceeded)?;
+			if let Some(limit) = &mut quota.rate_limit {
+				let now = frame_system::Pallet::<T>::block_number();
+				if now.saturating_sub(limit.period_start) >= limit.period {
+					limit.period_start = now;
+					limit.minted = Zero::zero();
+				}
+				limit.minted = limit
+					.minted
+					.checked_add(&value)
+					.filter(|minted| *minted <= limit.max_per_period)
+					.ok_or(Error::<T, I>::MintQuotaExceeded)?;
+			}
+			Ok(())
+		})
+	}
+
+	/// Whether `operation` is paused, globally or on its own.
+	pub fn is_paused(operation: Operation) -> bool {
+		Paused::<T, I>::get() || PausedOperations::<T, I>::contains_key(operation)
+	}
+
+	pub fn ensure_not_paused(operation: Operation) -> Result<(), Error<T, I>> {
+		if Self::is_paused(operation) {
+			return Err(Error::<T, I>::TokenPaused)
+		}
+		Ok(())
+	}
+
+	/// Whether `who` was frozen by `freeze_account`.
+	pub fn is_frozen(who: &T::AccountId) -> bool {
+		Frozen::<T, I>::contains_key(who)
+	}
+
+	pub fn ensure_not_frozen(who: &T::AccountId) -> Result<(), Error<T, I>> {
+		if Self::is_frozen(who) {
+			return Err(Error::<T, I>::AccountFrozen)
+		}
+		Ok(())
+	}
+
+	/// Whether `who` is on the allowlist.
+	pub fn is_allowlisted(who: &T::AccountId) -> bool {
+		Allowlist::<T, I>::contains_key(who)
+	}
+
+	/// Whether `who` collects fees of the token: `Config::TransferFeeAccount`,
+	/// `Config::FlashMintFeeReceiver` or `Config::FeeCollector`.
+	pub fn is_fee_account(who: &T::AccountId) -> bool {
+		*who == T::TransferFeeAccount::get() ||
+			*who == T::FlashMintFeeReceiver::get() ||
+			*who == T::FeeCollector::get()
+	}
+
+	/// Ensures `who` is on the allowlist, if it is enabled. The fee accounts are always allowed,
+	/// so that the fees do not fail the calls charging them.
+	pub fn ensure_allowlisted(who: &T::AccountId) -> Result<(), Error<T, I>> {
+		if AllowlistEnabled::<T, I>::get() &&
+			!Self::is_allowlisted(who) &&
+			!Self::is_fee_account(who)
+		{
+			return Err(Error::<T, I>::NotAllowlisted)
+		}
+		Ok(())
+	}
+
+	fn _add_to_allowlist(who: T::AccountId) {
+		Allowlist::<T, I>::insert(&who, ());
+		Self::deposit_event(Event::<T, I>::AddedToAllowlist { account: who });
+	}
+
+	fn _remove_from_allowlist(who: T::AccountId) {
+		Allowlist::<T, I>::remove(&who);
+		Self::deposit_event(Event::<T, I>::RemovedFromAllowlist { account: who });
+	}
+
+	/// Ensures `origin` is `Config::PauseOrigin` or signed by a holder of `Role::Pauser`.
+	fn ensure_pauser(origin: OriginFor<T>) -> DispatchResult {
+		if let Err(origin) = T::PauseOrigin::try_origin(origin) {
+			let sender = ensure_signed(origin)?;
+			Self::_check_role(Role::Pauser, &sender)?;
+		}
 		Ok(())
 	}
 }
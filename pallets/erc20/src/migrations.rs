@@ -0,0 +1,374 @@
+//! Storage migrations for the ERC20 pallet.
+
+use super::*;
+use frame_support::{
+	pallet_prelude::*,
+	storage_alias,
+	traits::{GetStorageVersion, OnRuntimeUpgrade},
+};
+
+/// Log target of the migrations.
+const LOG_TARGET: &str = "runtime::erc20";
+
+/// Storage items removed from the pallet that are still read by migrations.
+pub(crate) mod deprecated {
+	use super::*;
+
+	/// Accounts allowed to mint, replaced by `Roles` in version 3.
+	#[storage_alias]
+	pub(crate) type Minters<T: Config<I>, I: 'static> = StorageMap<
+		Pallet<T, I>,
+		Blake2_128Concat,
+		<T as frame_system::Config>::AccountId,
+		(),
+		ValueQuery,
+	>;
+
+	/// Number of `Minters`, replaced by `RoleMemberCount` in version 3.
+	#[storage_alias]
+	pub(crate) type MinterCount<T: Config<I>, I: 'static> =
+		StorageValue<Pallet<T, I>, u32, ValueQuery>;
+}
+
+pub mod v0 {
+	use super::*;
+	use frame_support::{storage::migration::move_pallet, StorageHasher, Twox128};
+
+	/// Moves the storage of the pallet from the prefix `OldName` to the prefix of the instance.
+	///
+	/// Runs when the instance has no storage version yet and storage is left under `OldName`,
+	/// so it must come before the versioned migrations, which then upgrade the moved storage.
+	pub struct MovePalletPrefix<T, I, OldName>(PhantomData<(T, I, OldName)>);
+
+	impl<T: Config<I>, I: 'static, OldName: Get<&'static str>> OnRuntimeUpgrade
+		for MovePalletPrefix<T, I, OldName>
+	{
+		fn on_runtime_upgrade() -> Weight {
+			let old_name = OldName::get();
+			let new_name = <Pallet<T, I> as PalletInfoAccess>::name();
+			if Pallet::<T, I>::on_chain_storage_version() != 0 || old_name == new_name {
+				return T::DbWeight::get().reads(1)
+			}
+
+			let prefix = Twox128::hash(old_name.as_bytes());
+			let keys = frame_support::storage::KeyPrefixIterator::new(
+				prefix.to_vec(),
+				prefix.to_vec(),
+				|_| Ok(()),
+			)
+			.count() as u64;
+			if keys == 0 {
+				return T::DbWeight::get().reads(2)
+			}
+
+			move_pallet(old_name.as_bytes(), new_name.as_bytes());
+
+			T::DbWeight::get().reads_writes(keys.saturating_mul(2).saturating_add(1), keys)
+		}
+
+		#[cfg(feature = "try-runtime")]
+		fn post_upgrade(_state: Vec<u8>) -> Result<(), sp_runtime::TryRuntimeError> {
+			ensure!(
+				!frame_support::storage::unhashed::contains_prefixed_key(&Twox128::hash(
+					OldName::get().as_bytes()
+				)),
+				"storage is left under the old pallet prefix"
+			);
+			Ok(())
+		}
+	}
+}
+
+pub mod v1 {
+	use super::*;
+
+	/// Recomputes `TotalSupply` from `Balances`.
+	///
+	/// Before version 1 `_mint` and `_burn` overwrote the supply with the last minted or burned
+	/// amount, so the stored value on already deployed chains cannot be trusted.
+	pub struct RecomputeTotalSupply<T, I = ()>(PhantomData<(T, I)>);
+
+	impl<T: Config<I>, I: 'static> OnRuntimeUpgrade for RecomputeTotalSupply<T, I> {
+		fn on_runtime_upgrade() -> Weight {
+			if Pallet::<T, I>::on_chain_storage_version() != 0 {
+				return T::DbWeight::get().reads(1)
+			}
+
+			let mut accounts = 0u64;
+			let mut total_supply = T::Balance::zero();
+			for (_, balance) in Balances::<T, I>::iter() {
+				accounts += 1;
+				total_supply = total_supply.saturating_add(balance);
+			}
+
+			TotalSupply::<T, I>::put(total_supply);
+			StorageVersion::new(1).put::<Pallet<T, I>>();
+			Pallet::<T, I>::deposit_event(Event::<T, I>::TotalSupplyRecomputed { total_supply });
+
+			T::DbWeight::get().reads_writes(accounts.saturating_add(1), 2)
+		}
+
+		#[cfg(feature = "try-runtime")]
+		fn post_upgrade(_state: Vec<u8>) -> Result<(), sp_runtime::TryRuntimeError> {
+			let total_supply = Balances::<T, I>::iter_values()
+				.fold(T::Balance::zero(), |acc, balance| acc.saturating_add(balance));
+			ensure!(
+				TotalSupply::<T, I>::get() == total_supply,
+				"total supply does not match the sum of balances"
+			);
+			ensure!(
+				Pallet::<T, I>::on_chain_storage_version() == 1,
+				"storage version was not bumped"
+			);
+			Ok(())
+		}
+	}
+}
+
+pub mod v2 {
+	use super::{deprecated::*, *};
+
+	/// Initialises `MinterCount` from the minters stored before the count was introduced.
+	pub struct InitMinterCount<T, I = ()>(PhantomData<(T, I)>);
+
+	impl<T: Config<I>, I: 'static> OnRuntimeUpgrade for InitMinterCount<T, I> {
+		fn on_runtime_upgrade() -> Weight {
+			if Pallet::<T, I>::on_chain_storage_version() != 1 {
+				return T::DbWeight::get().reads(1)
+			}
+
+			let minter_count = Minters::<T, I>::iter_keys().count() as u32;
+			MinterCount::<T, I>::put(minter_count);
+			StorageVersion::new(2).put::<Pallet<T, I>>();
+
+			T::DbWeight::get().reads_writes(u64::from(minter_count).saturating_add(1), 2)
+		}
+
+		#[cfg(feature = "try-runtime")]
+		fn post_upgrade(_state: Vec<u8>) -> Result<(), sp_runtime::TryRuntimeError> {
+			ensure!(
+				MinterCount::<T, I>::get() == Minters::<T, I>::iter_keys().count() as u32,
+				"minter count does not match the stored minters"
+			);
+			ensure!(
+				Pallet::<T, I>::on_chain_storage_version() == 2,
+				"storage version was not bumped"
+			);
+			Ok(())
+		}
+	}
+}
+
+pub mod v3 {
+	use super::*;
+
+	/// Moves the accounts of the former `Minters` map to `Role::Minter`.
+	///
+	/// At most `Config::MaxRoleMembers` minters are kept, the others are dropped and logged.
+	pub struct MintersToRoles<T, I = ()>(PhantomData<(T, I)>);
+
+	impl<T: Config<I>, I: 'static> OnRuntimeUpgrade for MintersToRoles<T, I> {
+		fn on_runtime_upgrade() -> Weight {
+			if Pallet::<T, I>::on_chain_storage_version() != 2 {
+				return T::DbWeight::get().reads(1)
+			}
+
+			let max_minters = T::MaxRoleMembers::get();
+			let mut accounts = 0u64;
+			let mut minters = 0u32;
+			for (who, _) in deprecated::Minters::<T, I>::drain() {
+				accounts += 1;
+				if minters >= max_minters {
+					frame_support::log::error!(
+						target: LOG_TARGET,
+						"dropping minter {:?}: more than {} minters",
+						who,
+						max_minters,
+					);
+					continue
+				}
+				Roles::<T, I>::insert(Role::Minter, who, ());
+				minters += 1;
+			}
+			RoleMemberCount::<T, I>::insert(Role::Minter, minters);
+			deprecated::MinterCount::<T, I>::kill();
+			StorageVersion::new(3).put::<Pallet<T, I>>();
+
+			T::DbWeight::get().reads_writes(
+				accounts.saturating_add(1),
+				accounts.saturating_add(u64::from(minters)).saturating_add(3),
+			)
+		}
+
+		#[cfg(feature = "try-runtime")]
+		fn pre_upgrade() -> Result<Vec<u8>, sp_runtime::TryRuntimeError> {
+			Ok((deprecated::Minters::<T, I>::iter_keys().count() as u32).encode())
+		}
+
+		#[cfg(feature = "try-runtime")]
+		fn post_upgrade(state: Vec<u8>) -> Result<(), sp_runtime::TryRuntimeError> {
+			let minters = u32::decode(&mut &state[..])
+				.map_err(|_| "failed to decode the number of minters")?;
+			ensure!(
+				RoleMemberCount::<T, I>::get(Role::Minter) == minters.min(T::MaxRoleMembers::get()),
+				"minters were not moved to roles"
+			);
+			ensure!(
+				deprecated::Minters::<T, I>::iter_keys().next().is_none(),
+				"minters were not removed"
+			);
+			ensure!(
+				Pallet::<T, I>::on_chain_storage_version() == 3,
+				"storage version was not bumped"
+			);
+			Ok(())
+		}
+	}
+}
+
+pub mod v4 {
+	use super::*;
+
+	/// Removes the accounts left below `Config::MinimumBalance` before accounts were reaped, with
+	/// their vesting schedules, and burns their dust.
+	pub struct ReapDustAccounts<T, I = ()>(PhantomData<(T, I)>);
+
+	impl<T: Config<I>, I: 'static> OnRuntimeUpgrade for ReapDustAccounts<T, I> {
+		fn on_runtime_upgrade() -> Weight {
+			if Pallet::<T, I>::on_chain_storage_version() != 3 {
+				return T::DbWeight::get().reads(1)
+			}
+
+			let mut accounts = 0u64;
+			let mut dust_accounts = Vec::new();
+			for (who, balance) in Balances::<T, I>::iter() {
+				accounts += 1;
+				if balance.is_zero() || balance < T::MinimumBalance::get() {
+					dust_accounts.push((who, balance));
+				}
+			}
+
+			let reaped = dust_accounts.len() as u64;
+			let mut dust = T::Balance::zero();
+			for (who, balance) in dust_accounts {
+				Balances::<T, I>::remove(&who);
+				Vesting::<T, I>::remove(&who);
+				Locked::<T, I>::remove(&who);
+				dust = dust.saturating_add(balance);
+			}
+			TotalSupply::<T, I>::mutate(|total_supply| {
+				*total_supply = total_supply.saturating_sub(dust)
+			});
+			StorageVersion::new(4).put::<Pallet<T, I>>();
+
+			T::DbWeight::get().reads_writes(
+				accounts.saturating_add(2),
+				reaped.saturating_mul(3).saturating_add(2),
+			)
+		}
+
+		#[cfg(feature = "try-runtime")]
+		fn post_upgrade(_state: Vec<u8>) -> Result<(), sp_runtime::TryRuntimeError> {
+			ensure!(
+				Balances::<T, I>::iter_values()
+					.all(|balance| !balance.is_zero() && balance >= T::MinimumBalance::get()),
+				"accounts below the minimum balance were not removed"
+			);
+			ensure!(
+				Pallet::<T, I>::on_chain_storage_version() == 4,
+				"storage version was not bumped"
+			);
+			Ok(())
+		}
+	}
+}
+
+pub mod v5 {
+	use super::*;
+
+	/// Removes the zero allowances left by `approve` and reserves `Config::AllowanceDeposit` for
+	/// the others. Allowances whose owner cannot pay the deposit are kept without one.
+	pub struct ReserveAllowanceDeposits<T, I = ()>(PhantomData<(T, I)>);
+
+	impl<T: Config<I>, I: 'static> OnRuntimeUpgrade for ReserveAllowanceDeposits<T, I> {
+		fn on_runtime_upgrade() -> Weight {
+			if Pallet::<T, I>::on_chain_storage_version() != 4 {
+				return T::DbWeight::get().reads(1)
+			}
+
+			let allowances = Allowances::<T, I>::iter().collect::<Vec<_>>();
+			let mut reads = allowances.len() as u64;
+			let mut writes = 0u64;
+			let deposit = T::AllowanceDeposit::get();
+			for (owner, spender, value) in allowances {
+				if value.is_zero() {
+					Allowances::<T, I>::remove(&owner, &spender);
+					writes += 1;
+				} else if !deposit.is_zero() {
+					reads += 1;
+					if T::Currency::reserve(&owner, deposit).is_ok() {
+						AllowanceDeposits::<T, I>::insert(
+							&owner,
+							&spender,
+							(owner.clone(), deposit),
+						);
+						writes += 2;
+					}
+				}
+			}
+			StorageVersion::new(5).put::<Pallet<T, I>>();
+
+			T::DbWeight::get().reads_writes(reads.saturating_add(1), writes.saturating_add(1))
+		}
+
+		#[cfg(feature = "try-runtime")]
+		fn post_upgrade(_state: Vec<u8>) -> Result<(), sp_runtime::TryRuntimeError> {
+			ensure!(
+				Allowances::<T, I>::iter_values().all(|value| !value.is_zero()),
+				"zero allowances were not removed"
+			);
+			ensure!(
+				Pallet::<T, I>::on_chain_storage_version() == 5,
+				"storage version was not bumped"
+			);
+			Ok(())
+		}
+	}
+}
+
+pub mod v6 {
+	use super::*;
+
+	/// Checkpoints the total supply at the upgrade, so that `get_past_total_supply` returns it
+	/// from the upgrade block on without waiting for the next mint or burn.
+	///
+	/// No history is recovered: blocks before the upgrade have no checkpoint and still read a
+	/// total supply of zero, as they read no votes for every delegate.
+	pub struct CheckpointTotalSupply<T, I = ()>(PhantomData<(T, I)>);
+
+	impl<T: Config<I>, I: 'static> OnRuntimeUpgrade for CheckpointTotalSupply<T, I> {
+		fn on_runtime_upgrade() -> Weight {
+			if Pallet::<T, I>::on_chain_storage_version() != 5 {
+				return T::DbWeight::get().reads(1)
+			}
+
+			Pallet::<T, I>::push_total_supply_checkpoint(TotalSupply::<T, I>::get());
+			StorageVersion::new(6).put::<Pallet<T, I>>();
+
+			T::DbWeight::get().reads_writes(4, 3)
+		}
+
+		#[cfg(feature = "try-runtime")]
+		fn post_upgrade(_state: Vec<u8>) -> Result<(), sp_runtime::TryRuntimeError> {
+			ensure!(
+				NumTotalSupplyCheckpoints::<T, I>::get() > 0,
+				"total supply was not checkpointed"
+			);
+			ensure!(
+				Pallet::<T, I>::on_chain_storage_version() == 6,
+				"storage version was not bumped"
+			);
+			Ok(())
+		}
+	}
+}
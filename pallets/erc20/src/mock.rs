@@ -1,9 +1,18 @@
 use crate as pallet_template;
-use frame_support::traits::{ConstU16, ConstU64};
+use crate::{payment::TokenFeeAdapter, Instance2};
+use codec::{Decode, Encode, MaxEncodedLen};
+use frame_support::{
+	parameter_types,
+	traits::{ConstU128, ConstU16, ConstU32, ConstU64, ConstU8},
+	weights::IdentityFee,
+};
+use frame_system::EnsureRoot;
+use scale_info::TypeInfo;
 use sp_core::H256;
 use sp_runtime::{
-	traits::{BlakeTwo256, IdentityLookup},
-	BuildStorage,
+	testing::{TestSignature, UintAuthorityId},
+	traits::{BlakeTwo256, ConvertInto, IdentityLookup},
+	BuildStorage, FixedU128, Permill, RuntimeDebug,
 };
 
 type Block = frame_system::mocking::MockBlock<Test>;
@@ -13,7 +22,10 @@ frame_support::construct_runtime!(
 	pub enum Test
 	{
 		System: frame_system,
+		NativeBalances: pallet_balances,
 		ERC20Module: pallet_template,
+		SecondERC20Module: pallet_template::<Instance2>,
+		TransactionPayment: pallet_transaction_payment,
 	}
 );
 
@@ -34,7 +46,7 @@ impl frame_system::Config for Test {
 	type BlockHashCount = ConstU64<250>;
 	type Version = ();
 	type PalletInfo = PalletInfo;
-	type AccountData = ();
+	type AccountData = pallet_balances::AccountData<u64>;
 	type OnNewAccount = ();
 	type OnKilledAccount = ();
 	type SystemWeightInfo = ();
@@ -43,10 +55,149 @@ impl frame_system::Config for Test {
 	type MaxConsumers = frame_support::traits::ConstU32<16>;
 }
 
+impl pallet_balances::Config for Test {
+	type MaxLocks = ();
+	type MaxReserves = ();
+	type ReserveIdentifier = [u8; 8];
+	type Balance = u64;
+	type RuntimeEvent = RuntimeEvent;
+	type DustRemoval = ();
+	type ExistentialDeposit = ConstU64<1>;
+	type AccountStore = System;
+	type WeightInfo = ();
+	type FreezeIdentifier = ();
+	type MaxFreezes = ();
+	type RuntimeHoldReason = ();
+	type MaxHolds = ();
+}
+
+/// Native balance of the accounts 1 to 5 at genesis.
+pub const NATIVE_BALANCE: u64 = 100;
+
+/// Native deposit reserved for each allowance of the first instance.
+pub const ALLOWANCE_DEPOSIT: u64 = 10;
+
+/// Reasons the tests put tokens on hold for.
+#[derive(
+	Clone,
+	Copy,
+	Encode,
+	Decode,
+	Eq,
+	PartialEq,
+	Ord,
+	PartialOrd,
+	RuntimeDebug,
+	MaxEncodedLen,
+	TypeInfo,
+)]
+pub enum HoldReason {
+	Escrow,
+	Bond,
+}
+
+/// Account receiving the transaction fees paid in the tokens.
+pub const FEE_COLLECTOR: u64 = 99;
+pub const TRANSFER_FEE_ACCOUNT: u64 = 98;
+
+parameter_types! {
+	pub const FeeCollector: u64 = FEE_COLLECTOR;
+	pub const TransferFeeAccount: u64 = TRANSFER_FEE_ACCOUNT;
+	pub FeeRate: FixedU128 = FixedU128::from_u32(2);
+	pub FlashMintFee: Permill = Permill::from_percent(1);
+}
+
+impl pallet_transaction_payment::Config for Test {
+	type RuntimeEvent = RuntimeEvent;
+	type OnChargeTransaction = TokenFeeAdapter<Test>;
+	type OperationalFeeMultiplier = ConstU8<5>;
+	type WeightToFee = IdentityFee<u64>;
+	type LengthToFee = IdentityFee<u64>;
+	type FeeMultiplierUpdate = ();
+}
+
 impl pallet_template::Config for Test {
 	type RuntimeEvent = RuntimeEvent;
+	type RuntimeCall = RuntimeCall;
 	type WeightInfo = ();
+	type Balance = u64;
+	type MinimumBalance = ConstU64<0>;
+	type MaxSupply = ConstU64<{ u64::MAX }>;
 	type Decimals = ConstU64<18>;
+	type StringLimit = ConstU32<50>;
+	type AdminOrigin = EnsureRoot<u64>;
+	type RoleAdminOrigin = EnsureRoot<u64>;
+	type MaxRoleMembers = ConstU32<3>;
+	type PauseOrigin = EnsureRoot<u64>;
+	type FreezeOrigin = EnsureRoot<u64>;
+	type MaxBatchSize = ConstU32<3>;
+	type MaxVestingSchedules = ConstU32<3>;
+	type BlockNumberToBalance = ConvertInto;
+	type MaxFlashMint = ConstU64<1000>;
+	type FlashMintFee = FlashMintFee;
+	type FlashMintFeeReceiver = FeeCollector;
+	type MaxFeeBps = ConstU16<100>;
+	type TransferFeeAccount = TransferFeeAccount;
+	type FeeCollector = FeeCollector;
+	type DefaultFeeRate = FeeRate;
+	type Currency = NativeBalances;
+	type AllowanceDeposit = ConstU64<ALLOWANCE_DEPOSIT>;
+	type RuntimeHoldReason = HoldReason;
+	type OffchainSignature = TestSignature;
+	type OffchainPublic = UintAuthorityId;
+	#[cfg(feature = "runtime-benchmarks")]
+	type BenchmarkHelper = PermitHelper;
+}
+
+/// Signs permits with `TestSignature`, which holds the signer and the message in clear.
+#[cfg(feature = "runtime-benchmarks")]
+pub struct PermitHelper;
+
+#[cfg(feature = "runtime-benchmarks")]
+impl pallet_template::BenchmarkHelper<UintAuthorityId, u64, TestSignature> for PermitHelper {
+	fn signer() -> (UintAuthorityId, u64) {
+		(UintAuthorityId(42), 42)
+	}
+
+	fn sign(signer: &UintAuthorityId, message: &[u8]) -> TestSignature {
+		TestSignature(signer.0, message.to_vec())
+	}
+}
+
+/// Supply cap of the second instance, above `u64::MAX` to exercise wide balances.
+pub const SECOND_MAX_SUPPLY: u128 = 1 << 80;
+
+impl pallet_template::Config<Instance2> for Test {
+	type RuntimeEvent = RuntimeEvent;
+	type RuntimeCall = RuntimeCall;
+	type WeightInfo = ();
+	type Balance = u128;
+	type MinimumBalance = ConstU128<5>;
+	type MaxSupply = ConstU128<SECOND_MAX_SUPPLY>;
+	type Decimals = ConstU64<6>;
+	type StringLimit = ConstU32<50>;
+	type AdminOrigin = EnsureRoot<u64>;
+	type RoleAdminOrigin = EnsureRoot<u64>;
+	type MaxRoleMembers = ConstU32<3>;
+	type PauseOrigin = EnsureRoot<u64>;
+	type FreezeOrigin = EnsureRoot<u64>;
+	type MaxBatchSize = ConstU32<3>;
+	type MaxVestingSchedules = ConstU32<3>;
+	type BlockNumberToBalance = ConvertInto;
+	type MaxFlashMint = ConstU128<1000>;
+	type FlashMintFee = FlashMintFee;
+	type FlashMintFeeReceiver = FeeCollector;
+	type MaxFeeBps = ConstU16<100>;
+	type TransferFeeAccount = TransferFeeAccount;
+	type FeeCollector = FeeCollector;
+	type DefaultFeeRate = FeeRate;
+	type Currency = NativeBalances;
+	type AllowanceDeposit = ConstU64<0>;
+	type RuntimeHoldReason = HoldReason;
+	type OffchainSignature = TestSignature;
+	type OffchainPublic = UintAuthorityId;
+	#[cfg(feature = "runtime-benchmarks")]
+	type BenchmarkHelper = PermitHelper;
 }
 
 // Build genesis storage according to the mock runtime.
@@ -55,15 +206,38 @@ pub fn new_test_ext() -> sp_io::TestExternalities {
 }
 
 pub struct ExtBuilder {
-	minters: Vec<u64>,
+	pub admins: Vec<u64>,
+	pub minters: Vec<u64>,
+	pub burners: Vec<u64>,
+	pub pausers: Vec<u64>,
+	pub balances: Vec<(u64, u64)>,
+	pub second_minters: Vec<u64>,
 }
 
 impl ExtBuilder {
 	pub fn build(self) -> sp_io::TestExternalities {
 		let mut t = frame_system::GenesisConfig::<Test>::default().build_storage().unwrap();
-		crate::GenesisConfig::<Test> { minters: self.minters }
-			.assimilate_storage(&mut t)
-			.unwrap();
+		pallet_balances::GenesisConfig::<Test> {
+			balances: (1..=5).map(|who| (who, NATIVE_BALANCE)).collect(),
+		}
+		.assimilate_storage(&mut t)
+		.unwrap();
+		crate::GenesisConfig::<Test> {
+			admins: self.admins,
+			minters: self.minters,
+			burners: self.burners,
+			pausers: self.pausers,
+			balances: self.balances,
+			..Default::default()
+		}
+		.assimilate_storage(&mut t)
+		.unwrap();
+		crate::GenesisConfig::<Test, Instance2> {
+			minters: self.second_minters,
+			..Default::default()
+		}
+		.assimilate_storage(&mut t)
+		.unwrap();
 
 		let mut ext = sp_io::TestExternalities::new(t);
 		ext.execute_with(|| System::set_block_number(1));
@@ -78,6 +252,13 @@ impl ExtBuilder {
 
 impl Default for ExtBuilder {
 	fn default() -> Self {
-		Self { minters: vec![1] }
+		Self {
+			admins: vec![10],
+			minters: vec![1],
+			burners: vec![1],
+			pausers: vec![3],
+			balances: vec![],
+			second_minters: vec![2],
+		}
 	}
 }
@@ -0,0 +1,78 @@
+//! Payment of transaction fees in the token.
+//!
+//! `TokenFeeAdapter` can be used as `pallet_transaction_payment::Config::OnChargeTransaction`.
+//! Fees are computed in the native currency as usual and converted to the token at
+//! `Pallet::fee_rate`.
+
+use super::*;
+use core::marker::PhantomData;
+use frame_support::traits::{
+	fungible::{Balanced, Credit, Debt},
+	tokens::{Fortitude, Precision, Preservation},
+};
+use pallet_transaction_payment::OnChargeTransaction;
+use sp_runtime::{
+	traits::{DispatchInfoOf, PostDispatchInfoOf},
+	transaction_validity::{InvalidTransaction, TransactionValidityError},
+};
+
+/// Charges transaction fees in the token of the `I` instance and pays them to
+/// `Config::FeeCollector`, refunding the fee of unused weight.
+pub struct TokenFeeAdapter<T, I = ()>(PhantomData<(T, I)>);
+
+impl<T, I: 'static> OnChargeTransaction<T> for TokenFeeAdapter<T, I>
+where
+	T: Config<I> + pallet_transaction_payment::Config,
+	T::Balance: FixedPointOperand,
+{
+	type Balance = T::Balance;
+	type LiquidityInfo = Option<Credit<T::AccountId, Pallet<T, I>>>;
+
+	fn withdraw_fee(
+		who: &T::AccountId,
+		_call: &<T as frame_system::Config>::RuntimeCall,
+		_dispatch_info: &DispatchInfoOf<<T as frame_system::Config>::RuntimeCall>,
+		fee: Self::Balance,
+		_tip: Self::Balance,
+	) -> Result<Self::LiquidityInfo, TransactionValidityError> {
+		if fee.is_zero() {
+			return Ok(None)
+		}
+
+		match Pallet::<T, I>::withdraw(
+			who,
+			Pallet::<T, I>::convert_fee(fee),
+			Precision::Exact,
+			Preservation::Preserve,
+			Fortitude::Polite,
+		) {
+			Ok(credit) => Ok(Some(credit)),
+			Err(_) => Err(InvalidTransaction::Payment.into()),
+		}
+	}
+
+	fn correct_and_deposit_fee(
+		who: &T::AccountId,
+		_dispatch_info: &DispatchInfoOf<<T as frame_system::Config>::RuntimeCall>,
+		_post_info: &PostDispatchInfoOf<<T as frame_system::Config>::RuntimeCall>,
+		corrected_fee: Self::Balance,
+		_tip: Self::Balance,
+		already_withdrawn: Self::LiquidityInfo,
+	) -> Result<(), TransactionValidityError> {
+		let Some(paid) = already_withdrawn else { return Ok(()) };
+
+		let refund = paid.peek().saturating_sub(Pallet::<T, I>::convert_fee(corrected_fee));
+		let refund = Pallet::<T, I>::deposit(who, refund, Precision::BestEffort)
+			.unwrap_or_else(|_| Debt::<T::AccountId, Pallet<T, I>>::zero());
+		let fee = paid.offset(refund).same().map_err(|_| InvalidTransaction::Payment)?;
+
+		let amount = fee.peek();
+		// The fee is burned if the collector cannot receive it.
+		let _ = Pallet::<T, I>::resolve(&T::FeeCollector::get(), fee);
+		Pallet::<T, I>::deposit_event(Event::<T, I>::TransactionFeePaid {
+			who: who.clone(),
+			fee: amount,
+		});
+		Ok(())
+	}
+}
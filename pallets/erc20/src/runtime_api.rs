@@ -0,0 +1,136 @@
+//! Runtime APIs of the ERC20 pallet.
+//!
+//! A runtime can host several instances of the pallet, so every call takes a `TokenId` chosen by
+//! the runtime to select the instance.
+
+use crate::{Role, SnapshotId};
+use codec::Codec;
+use frame_support::dispatch::Vec;
+
+sp_api::decl_runtime_apis! {
+	/// Queries over the token access control.
+	pub trait AccessControlApi<TokenId, AccountId>
+	where
+		TokenId: Codec,
+		AccountId: Codec,
+	{
+		/// Whether `account` holds `role`.
+		fn has_role(token: TokenId, role: Role, account: AccountId) -> bool;
+
+		/// All accounts holding `role`.
+		fn role_members(token: TokenId, role: Role) -> Vec<AccountId>;
+	}
+
+	/// Queries over the token metadata set at genesis or by `set_metadata`.
+	pub trait MetadataApi<TokenId>
+	where
+		TokenId: Codec,
+	{
+		/// Name of the token.
+		fn name(token: TokenId) -> Vec<u8>;
+
+		/// Symbol of the token.
+		fn symbol(token: TokenId) -> Vec<u8>;
+
+		/// Number of decimals of the token.
+		fn decimals(token: TokenId) -> u64;
+	}
+
+	/// Queries over the accounts frozen by `freeze_account`.
+	pub trait FreezeApi<TokenId, AccountId>
+	where
+		TokenId: Codec,
+		AccountId: Codec,
+	{
+		/// Whether `who` is frozen.
+		fn is_frozen(token: TokenId, who: AccountId) -> bool;
+	}
+
+	/// Queries over the token supply.
+	pub trait SupplyApi<TokenId, Balance>
+	where
+		TokenId: Codec,
+		Balance: Codec,
+	{
+		/// Current total supply.
+		fn total_supply(token: TokenId) -> Balance;
+
+		/// Amount that can still be minted before reaching the supply cap.
+		fn remaining_mintable(token: TokenId) -> Balance;
+	}
+
+	/// Queries over the balances of the token.
+	pub trait BalancesApi<TokenId, AccountId, Balance>
+	where
+		TokenId: Codec,
+		AccountId: Codec,
+		Balance: Codec,
+	{
+		/// Balance of `who` that can be transferred.
+		fn free_balance(token: TokenId, who: AccountId) -> Balance;
+
+		/// Balance of `who` that is reserved or on hold and cannot be transferred.
+		fn reserved_balance(token: TokenId, who: AccountId) -> Balance;
+	}
+
+	/// Queries over the balance and total supply snapshots.
+	pub trait SnapshotApi<TokenId, AccountId, Balance>
+	where
+		TokenId: Codec,
+		AccountId: Codec,
+		Balance: Codec,
+	{
+		/// Id of the latest snapshot, 0 before the first one.
+		fn current_snapshot_id(token: TokenId) -> SnapshotId;
+
+		/// Balance of `who`, reserved balance included, at snapshot `id`, `None` if no such
+		/// snapshot was taken.
+		fn balance_of_at(token: TokenId, who: AccountId, id: SnapshotId) -> Option<Balance>;
+
+		/// Total supply at snapshot `id`, `None` if no such snapshot was taken.
+		fn total_supply_at(token: TokenId, id: SnapshotId) -> Option<Balance>;
+	}
+
+	/// Queries over the voting power delegated to each account, for on-chain governance.
+	pub trait VotesApi<TokenId, AccountId, Balance, BlockNumber>
+	where
+		TokenId: Codec,
+		AccountId: Codec,
+		Balance: Codec,
+		BlockNumber: Codec,
+	{
+		/// Account `who` delegates its voting power to, if any.
+		fn delegates(token: TokenId, who: AccountId) -> Option<AccountId>;
+
+		/// Current voting power of `who`.
+		fn get_votes(token: TokenId, who: AccountId) -> Balance;
+
+		/// Voting power of `who` at the end of `block`, `None` if `block` is not in the past.
+		fn get_past_votes(token: TokenId, who: AccountId, block: BlockNumber) -> Option<Balance>;
+
+		/// Total supply at the end of `block`, `None` if `block` is not in the past.
+		fn get_past_total_supply(token: TokenId, block: BlockNumber) -> Option<Balance>;
+	}
+
+	/// Queries for signing `permit` calls.
+	pub trait PermitApi<TokenId, AccountId, Balance, BlockNumber>
+	where
+		TokenId: Codec,
+		AccountId: Codec,
+		Balance: Codec,
+		BlockNumber: Codec,
+	{
+		/// Nonce the next permit of `owner` is signed with.
+		fn permit_nonce(token: TokenId, owner: AccountId) -> u64;
+
+		/// SCALE encoded payload `owner` has to sign to permit `spender` to spend `value` until
+		/// the `deadline` block.
+		fn permit_payload(
+			token: TokenId,
+			owner: AccountId,
+			spender: AccountId,
+			value: Balance,
+			deadline: BlockNumber,
+		) -> Vec<u8>;
+	}
+}
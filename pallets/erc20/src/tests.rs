@@ -1,5 +1,31 @@
-use crate::{mock::*, Error, Event};
-use frame_support::{assert_noop, assert_ok};
+use crate::{
+	migrations::{self, deprecated},
+	mock::*,
+	payment::TokenFeeAdapter,
+	AllowanceDeposits, Allowances, Balances, Error, Event, Instance2, Locked, MintQuota,
+	MintRateLimit, NumAccountSnapshots, Operation, Role, TotalSupply, VestingSchedule,
+};
+use codec::Encode;
+use frame_support::{
+	assert_noop, assert_ok,
+	dispatch::{DispatchInfo, PostDispatchInfo},
+	parameter_types,
+	traits::{
+		tokens::{
+			fungible::{Balanced, Inspect, InspectHold, Mutate, MutateHold, Unbalanced},
+			DepositConsequence, Fortitude, Precision, Preservation, Provenance,
+			WithdrawConsequence,
+		},
+		BalanceStatus, GetStorageVersion, OnRuntimeUpgrade, ReservableCurrency, StorageVersion,
+	},
+	weights::Weight,
+	StorageHasher, Twox128,
+};
+use pallet_transaction_payment::OnChargeTransaction;
+use sp_runtime::{
+	testing::TestSignature, transaction_validity::InvalidTransaction, BuildStorage, DispatchError,
+	FixedU128, TokenError,
+};
 
 /// Should mint tokens correctly
 #[test]
@@ -14,7 +40,10 @@ fn mint_ok() {
 #[test]
 fn mint_bad_authority() {
 	ExtBuilder::default().build_and_execute(|| {
-		assert_noop!(ERC20Module::mint(RuntimeOrigin::signed(2), 42), Error::<Test>::AccessControl);
+		assert_noop!(
+			ERC20Module::mint(RuntimeOrigin::signed(2), 42),
+			Error::<Test>::AccessControl(Role::Minter)
+		);
 		assert_eq!(ERC20Module::balance_of(&2), 0);
 	});
 }
@@ -113,3 +142,1912 @@ fn burn_low_balance() {
 		assert_eq!(ERC20Module::balance_of(&1), 42);
 	})
 }
+
+/// Should keep balances of different instances apart
+#[test]
+fn instances_are_isolated() {
+	ExtBuilder::default().build_and_execute(|| {
+		assert_ok!(ERC20Module::mint(RuntimeOrigin::signed(1), 42));
+		assert_ok!(SecondERC20Module::mint(RuntimeOrigin::signed(2), 7));
+		assert_eq!(ERC20Module::balance_of(&1), 42);
+		assert_eq!(ERC20Module::balance_of(&2), 0);
+		assert_eq!(SecondERC20Module::balance_of(&1), 0);
+		assert_eq!(SecondERC20Module::balance_of(&2), 7);
+	});
+}
+
+/// Should check minters per instance
+#[test]
+fn instances_have_own_minters() {
+	ExtBuilder::default().build_and_execute(|| {
+		assert_noop!(
+			SecondERC20Module::mint(RuntimeOrigin::signed(1), 42),
+			Error::<Test, Instance2>::AccessControl(Role::Minter)
+		);
+		assert_noop!(
+			ERC20Module::mint(RuntimeOrigin::signed(2), 42),
+			Error::<Test>::AccessControl(Role::Minter)
+		);
+	});
+}
+
+/// Should handle amounts above u64::MAX when the instance uses a wider balance type
+#[test]
+fn wide_balance_ok() {
+	ExtBuilder::default().build_and_execute(|| {
+		let value = u64::MAX as u128 + 1;
+		assert_ok!(SecondERC20Module::mint(RuntimeOrigin::signed(2), value));
+		assert_ok!(SecondERC20Module::transfer(RuntimeOrigin::signed(2), 3, value));
+		assert_eq!(SecondERC20Module::balance_of(&3), value);
+	});
+}
+
+/// Should keep total supply in sync with mint and burn
+#[test]
+fn total_supply_ok() {
+	ExtBuilder::default().build_and_execute(|| {
+		assert_ok!(ERC20Module::mint(RuntimeOrigin::signed(1), 42));
+		assert_ok!(ERC20Module::mint(RuntimeOrigin::signed(1), 8));
+		assert_eq!(ERC20Module::total_supply(), 50);
+		assert_ok!(ERC20Module::burn(RuntimeOrigin::signed(1), 20));
+		assert_eq!(ERC20Module::total_supply(), 30);
+		assert_ok!(ERC20Module::transfer(RuntimeOrigin::signed(1), 2, 10));
+		assert_eq!(ERC20Module::total_supply(), 30);
+	});
+}
+
+/// Should revert if mint overflows total supply
+#[test]
+fn mint_supply_overflow() {
+	ExtBuilder::default().build_and_execute(|| {
+		assert_ok!(ERC20Module::mint(RuntimeOrigin::signed(1), u64::MAX));
+		assert_ok!(ERC20Module::transfer(RuntimeOrigin::signed(1), 2, u64::MAX));
+		assert_noop!(
+			ERC20Module::mint(RuntimeOrigin::signed(1), 1),
+			Error::<Test>::StorageOverflow
+		);
+	});
+}
+
+/// Should recompute total supply from balances on upgrade
+#[test]
+fn migration_v1_recomputes_total_supply() {
+	ExtBuilder::default().build_and_execute(|| {
+		Balances::<Test>::insert(1u64, 40u64);
+		Balances::<Test>::insert(2u64, 2u64);
+		TotalSupply::<Test>::put(7u64);
+		StorageVersion::new(0).put::<ERC20Module>();
+
+		let weight = migrations::v1::RecomputeTotalSupply::<Test>::on_runtime_upgrade();
+
+		assert!(weight != Weight::zero());
+		assert_eq!(ERC20Module::total_supply(), 42);
+		assert_eq!(ERC20Module::on_chain_storage_version(), 1);
+		System::assert_last_event(Event::<Test>::TotalSupplyRecomputed { total_supply: 42 }.into());
+		// A second run is a no-op.
+		TotalSupply::<Test>::put(7u64);
+		migrations::v1::RecomputeTotalSupply::<Test>::on_runtime_upgrade();
+		assert_eq!(ERC20Module::total_supply(), 7);
+	});
+}
+
+/// Should move the storage left under the former pallet name to the instance
+#[test]
+fn migration_v0_moves_pallet_prefix() {
+	use frame_support::storage::{migration, unhashed};
+
+	parameter_types! {
+		pub const OldPalletName: &'static str = "ERCModule";
+	}
+
+	ExtBuilder::default().build_and_execute(|| {
+		let _ = unhashed::clear_prefix(&Twox128::hash(b"ERC20Module"), None, None);
+		migration::put_storage_value(b"ERCModule", b"TotalSupply", &[], 42u64);
+		assert_eq!(ERC20Module::on_chain_storage_version(), 0);
+
+		migrations::v0::MovePalletPrefix::<Test, (), OldPalletName>::on_runtime_upgrade();
+
+		assert_eq!(ERC20Module::total_supply(), 42);
+		assert!(!unhashed::contains_prefixed_key(&Twox128::hash(b"ERCModule")));
+		// A second run is a no-op.
+		migration::put_storage_value(b"ERCModule", b"TotalSupply", &[], 7u64);
+		StorageVersion::new(1).put::<ERC20Module>();
+		migrations::v0::MovePalletPrefix::<Test, (), OldPalletName>::on_runtime_upgrade();
+		assert_eq!(ERC20Module::total_supply(), 42);
+	});
+}
+
+/// Should fall back to the configured decimals until metadata is set
+#[test]
+fn decimals_default() {
+	ExtBuilder::default().build_and_execute(|| {
+		assert_eq!(ERC20Module::decimals(), 18);
+		assert_eq!(SecondERC20Module::decimals(), 6);
+	});
+}
+
+/// Should set metadata from the admin origin
+#[test]
+fn set_metadata_ok() {
+	ExtBuilder::default().build_and_execute(|| {
+		assert_ok!(ERC20Module::set_metadata(
+			RuntimeOrigin::root(),
+			b"Test Token".to_vec(),
+			b"TST".to_vec(),
+			12
+		));
+		assert_eq!(ERC20Module::name().into_inner(), b"Test Token".to_vec());
+		assert_eq!(ERC20Module::symbol().into_inner(), b"TST".to_vec());
+		assert_eq!(ERC20Module::decimals(), 12);
+		System::assert_last_event(
+			Event::<Test>::MetadataSet {
+				name: b"Test Token".to_vec(),
+				symbol: b"TST".to_vec(),
+				decimals: 12,
+			}
+			.into(),
+		);
+		assert_eq!(SecondERC20Module::decimals(), 6);
+	});
+}
+
+/// Should revert if metadata is set by a non-admin origin
+#[test]
+fn set_metadata_bad_origin() {
+	ExtBuilder::default().build_and_execute(|| {
+		assert_noop!(
+			ERC20Module::set_metadata(RuntimeOrigin::signed(1), b"T".to_vec(), b"T".to_vec(), 1),
+			DispatchError::BadOrigin
+		);
+	});
+}
+
+/// Should revert if name or symbol exceeds the string limit
+#[test]
+fn set_metadata_too_long() {
+	ExtBuilder::default().build_and_execute(|| {
+		assert_noop!(
+			ERC20Module::set_metadata(RuntimeOrigin::root(), vec![b'N'; 51], b"T".to_vec(), 1),
+			Error::<Test>::BadMetadata
+		);
+		assert_noop!(
+			ERC20Module::set_metadata(RuntimeOrigin::root(), b"T".to_vec(), vec![b'S'; 51], 1),
+			Error::<Test>::BadMetadata
+		);
+	});
+}
+
+/// Should add a minter from the role admin origin
+#[test]
+fn add_minter_ok() {
+	ExtBuilder::default().build_and_execute(|| {
+		assert_ok!(ERC20Module::add_minter(RuntimeOrigin::root(), 2));
+		assert_eq!(ERC20Module::role_member_count(Role::Minter), 2);
+		System::assert_last_event(
+			Event::<Test>::RoleGranted { role: Role::Minter, account: 2, sender: None }.into(),
+		);
+		assert_ok!(ERC20Module::mint(RuntimeOrigin::signed(2), 42));
+		assert_eq!(ERC20Module::balance_of(&2), 42);
+	});
+}
+
+/// Should revert if minters are managed by an account without the admin role
+#[test]
+fn add_minter_bad_origin() {
+	ExtBuilder::default().build_and_execute(|| {
+		assert_noop!(
+			ERC20Module::add_minter(RuntimeOrigin::signed(1), 2),
+			Error::<Test>::AccessControl(Role::Admin)
+		);
+		assert_noop!(
+			ERC20Module::remove_minter(RuntimeOrigin::signed(1), 1),
+			Error::<Test>::AccessControl(Role::Admin)
+		);
+	});
+}
+
+/// Should revert if the role member limit is reached
+#[test]
+fn add_minter_too_many() {
+	ExtBuilder::default().build_and_execute(|| {
+		assert_ok!(ERC20Module::add_minter(RuntimeOrigin::root(), 2));
+		assert_ok!(ERC20Module::add_minter(RuntimeOrigin::root(), 3));
+		assert_noop!(
+			ERC20Module::add_minter(RuntimeOrigin::root(), 4),
+			Error::<Test>::TooManyRoleMembers
+		);
+	});
+}
+
+/// Should remove a minter and revoke its right to mint
+#[test]
+fn remove_minter_ok() {
+	ExtBuilder::default().build_and_execute(|| {
+		assert_ok!(ERC20Module::remove_minter(RuntimeOrigin::root(), 1));
+		assert_eq!(ERC20Module::role_member_count(Role::Minter), 0);
+		System::assert_last_event(
+			Event::<Test>::RoleRevoked { role: Role::Minter, account: 1, sender: None }.into(),
+		);
+		assert_noop!(
+			ERC20Module::mint(RuntimeOrigin::signed(1), 42),
+			Error::<Test>::AccessControl(Role::Minter)
+		);
+	});
+}
+
+/// Should grant and revoke roles from an account holding the admin role
+#[test]
+fn grant_role_ok() {
+	ExtBuilder::default().build_and_execute(|| {
+		assert_ok!(ERC20Module::grant_role(RuntimeOrigin::signed(10), Role::Pauser, 3));
+		assert!(ERC20Module::has_role(Role::Pauser, &3));
+		System::assert_last_event(
+			Event::<Test>::RoleGranted { role: Role::Pauser, account: 3, sender: Some(10) }.into(),
+		);
+		// Granting twice is a no-op.
+		assert_ok!(ERC20Module::grant_role(RuntimeOrigin::signed(10), Role::Pauser, 3));
+		assert_eq!(ERC20Module::role_member_count(Role::Pauser), 1);
+
+		assert_ok!(ERC20Module::revoke_role(RuntimeOrigin::signed(10), Role::Pauser, 3));
+		assert!(!ERC20Module::has_role(Role::Pauser, &3));
+		System::assert_last_event(
+			Event::<Test>::RoleRevoked { role: Role::Pauser, account: 3, sender: Some(10) }.into(),
+		);
+	});
+}
+
+/// Should revert if the caller lacks the admin role of the role
+#[test]
+fn grant_role_bad_authority() {
+	ExtBuilder::default().build_and_execute(|| {
+		assert_noop!(
+			ERC20Module::grant_role(RuntimeOrigin::signed(1), Role::Burner, 2),
+			Error::<Test>::AccessControl(Role::Admin)
+		);
+		assert_noop!(
+			ERC20Module::grant_role(RuntimeOrigin::none(), Role::Burner, 2),
+			DispatchError::BadOrigin
+		);
+	});
+}
+
+/// Should let the role admin of a role grant it
+#[test]
+fn set_role_admin_ok() {
+	ExtBuilder::default().build_and_execute(|| {
+		assert_ok!(ERC20Module::set_role_admin(RuntimeOrigin::root(), Role::Burner, Role::Minter));
+		assert_eq!(ERC20Module::role_admin(Role::Burner), Role::Minter);
+		System::assert_last_event(
+			Event::<Test>::RoleAdminChanged {
+				role: Role::Burner,
+				previous_admin_role: Role::Admin,
+				new_admin_role: Role::Minter,
+			}
+			.into(),
+		);
+		assert_ok!(ERC20Module::grant_role(RuntimeOrigin::signed(1), Role::Burner, 2));
+		assert_noop!(
+			ERC20Module::grant_role(RuntimeOrigin::signed(10), Role::Burner, 3),
+			Error::<Test>::AccessControl(Role::Minter)
+		);
+		assert_noop!(
+			ERC20Module::set_role_admin(RuntimeOrigin::signed(10), Role::Burner, Role::Admin),
+			DispatchError::BadOrigin
+		);
+	});
+}
+
+/// Should let an account give up its own role
+#[test]
+fn renounce_role_ok() {
+	ExtBuilder::default().build_and_execute(|| {
+		assert_ok!(ERC20Module::renounce_role(RuntimeOrigin::signed(1), Role::Burner));
+		assert!(!ERC20Module::has_role(Role::Burner, &1));
+		System::assert_last_event(
+			Event::<Test>::RoleRevoked { role: Role::Burner, account: 1, sender: Some(1) }.into(),
+		);
+		assert_ok!(ERC20Module::mint(RuntimeOrigin::signed(1), 42));
+		assert_noop!(
+			ERC20Module::burn(RuntimeOrigin::signed(1), 42),
+			Error::<Test>::AccessControl(Role::Burner)
+		);
+	});
+}
+
+/// Should list the members of a role
+#[test]
+fn role_members_ok() {
+	ExtBuilder::default().build_and_execute(|| {
+		assert_ok!(ERC20Module::add_minter(RuntimeOrigin::root(), 2));
+		let mut minters = ERC20Module::role_members(Role::Minter);
+		minters.sort();
+		assert_eq!(minters, vec![1, 2]);
+		assert_eq!(ERC20Module::role_members(Role::Admin), vec![10]);
+	});
+}
+
+/// Should initialise the minter count on upgrade
+#[test]
+fn migration_v2_inits_minter_count() {
+	ExtBuilder::default().build_and_execute(|| {
+		deprecated::Minters::<Test>::insert(1u64, ());
+		deprecated::Minters::<Test>::insert(2u64, ());
+		StorageVersion::new(1).put::<ERC20Module>();
+
+		migrations::v2::InitMinterCount::<Test>::on_runtime_upgrade();
+
+		assert_eq!(deprecated::MinterCount::<Test>::get(), 2);
+		assert_eq!(ERC20Module::on_chain_storage_version(), 2);
+	});
+}
+
+/// Should move the former minters to the minter role on upgrade
+#[test]
+fn migration_v3_moves_minters_to_roles() {
+	ExtBuilder { minters: vec![], ..Default::default() }.build_and_execute(|| {
+		deprecated::Minters::<Test>::insert(4u64, ());
+		deprecated::Minters::<Test>::insert(5u64, ());
+		deprecated::MinterCount::<Test>::put(2u32);
+		StorageVersion::new(2).put::<ERC20Module>();
+
+		migrations::v3::MintersToRoles::<Test>::on_runtime_upgrade();
+
+		assert!(ERC20Module::has_role(Role::Minter, &4));
+		assert!(ERC20Module::has_role(Role::Minter, &5));
+		assert_eq!(ERC20Module::role_member_count(Role::Minter), 2);
+		assert!(!deprecated::Minters::<Test>::contains_key(4u64));
+		assert!(!deprecated::MinterCount::<Test>::exists());
+		assert_eq!(ERC20Module::on_chain_storage_version(), 3);
+	});
+}
+
+/// Should keep at most `MaxRoleMembers` of the former minters on upgrade
+#[test]
+fn migration_v3_bounds_minters() {
+	ExtBuilder { minters: vec![], ..Default::default() }.build_and_execute(|| {
+		for who in 1u64..=5 {
+			deprecated::Minters::<Test>::insert(who, ());
+		}
+		deprecated::MinterCount::<Test>::put(5u32);
+		StorageVersion::new(2).put::<ERC20Module>();
+
+		migrations::v3::MintersToRoles::<Test>::on_runtime_upgrade();
+
+		assert_eq!(ERC20Module::role_member_count(Role::Minter), 3);
+		assert_eq!(ERC20Module::role_members(Role::Minter).len(), 3);
+		assert_eq!(deprecated::Minters::<Test>::iter_keys().count(), 0);
+		assert_eq!(ERC20Module::on_chain_storage_version(), 3);
+	});
+}
+
+/// Should block every operation while globally paused
+#[test]
+fn pause_all_ok() {
+	ExtBuilder::default().build_and_execute(|| {
+		assert_ok!(ERC20Module::mint(RuntimeOrigin::signed(1), 42));
+		assert_ok!(ERC20Module::approve(RuntimeOrigin::signed(1), 2, 42));
+		assert_ok!(ERC20Module::pause(RuntimeOrigin::signed(3), None));
+		System::assert_last_event(Event::<Test>::Paused { operation: None }.into());
+
+		assert_noop!(
+			ERC20Module::transfer(RuntimeOrigin::signed(1), 2, 1),
+			Error::<Test>::TokenPaused
+		);
+		assert_noop!(
+			ERC20Module::transfer_from(RuntimeOrigin::signed(2), 1, 3, 1),
+			Error::<Test>::TokenPaused
+		);
+		assert_noop!(ERC20Module::mint(RuntimeOrigin::signed(1), 1), Error::<Test>::TokenPaused);
+		assert_noop!(ERC20Module::burn(RuntimeOrigin::signed(1), 1), Error::<Test>::TokenPaused);
+
+		assert_ok!(ERC20Module::unpause(RuntimeOrigin::signed(3), None));
+		System::assert_last_event(Event::<Test>::Unpaused { operation: None }.into());
+		assert_ok!(ERC20Module::transfer(RuntimeOrigin::signed(1), 2, 1));
+	});
+}
+
+/// Should block only the paused operation
+#[test]
+fn pause_operation_ok() {
+	ExtBuilder::default().build_and_execute(|| {
+		assert_ok!(ERC20Module::mint(RuntimeOrigin::signed(1), 42));
+		assert_ok!(ERC20Module::pause(RuntimeOrigin::root(), Some(Operation::Mint)));
+
+		assert_noop!(ERC20Module::mint(RuntimeOrigin::signed(1), 1), Error::<Test>::TokenPaused);
+		assert_ok!(ERC20Module::transfer(RuntimeOrigin::signed(1), 2, 1));
+		assert_ok!(ERC20Module::burn(RuntimeOrigin::signed(1), 1));
+
+		// Lifting the global pause keeps operations paused on their own.
+		assert_ok!(ERC20Module::unpause(RuntimeOrigin::root(), None));
+		assert!(ERC20Module::is_paused(Operation::Mint));
+		assert_ok!(ERC20Module::unpause(RuntimeOrigin::root(), Some(Operation::Mint)));
+		assert_ok!(ERC20Module::mint(RuntimeOrigin::signed(1), 1));
+	});
+}
+
+/// Should revert if the caller cannot pause
+#[test]
+fn pause_bad_authority() {
+	ExtBuilder::default().build_and_execute(|| {
+		assert_noop!(
+			ERC20Module::pause(RuntimeOrigin::signed(1), None),
+			Error::<Test>::AccessControl(Role::Pauser)
+		);
+		assert_noop!(
+			ERC20Module::unpause(RuntimeOrigin::signed(1), None),
+			Error::<Test>::AccessControl(Role::Pauser)
+		);
+	});
+}
+
+/// Should revert if mint exceeds the supply cap
+#[test]
+fn mint_supply_cap_exceeded() {
+	ExtBuilder::default().build_and_execute(|| {
+		assert_ok!(SecondERC20Module::mint(RuntimeOrigin::signed(2), SECOND_MAX_SUPPLY - 1));
+		assert_eq!(SecondERC20Module::remaining_mintable(), 1);
+		assert_noop!(
+			SecondERC20Module::mint(RuntimeOrigin::signed(2), 2),
+			Error::<Test, Instance2>::SupplyCapExceeded
+		);
+		assert_ok!(SecondERC20Module::mint(RuntimeOrigin::signed(2), 1));
+		assert_eq!(SecondERC20Module::remaining_mintable(), 0);
+	});
+}
+
+/// Should mint genesis balances into the total supply
+#[test]
+fn genesis_balances_ok() {
+	ExtBuilder { balances: vec![(1, 40), (2, 2)], ..Default::default() }.build_and_execute(|| {
+		assert_eq!(ERC20Module::balance_of(&1), 40);
+		assert_eq!(ERC20Module::balance_of(&2), 2);
+		assert_eq!(ERC20Module::total_supply(), 42);
+	});
+}
+
+/// Should refuse genesis balances above the supply cap
+#[test]
+#[should_panic(expected = "genesis balances must not exceed the supply cap")]
+fn genesis_balances_above_cap() {
+	let mut t = frame_system::GenesisConfig::<Test>::default().build_storage().unwrap();
+	crate::GenesisConfig::<Test, Instance2> {
+		balances: vec![(1, SECOND_MAX_SUPPLY), (2, 1)],
+		..Default::default()
+	}
+	.assimilate_storage(&mut t)
+	.unwrap();
+}
+
+/// Should refuse genesis balances below the minimum balance
+#[test]
+#[should_panic(expected = "genesis balances must not be below the minimum balance")]
+fn genesis_balances_below_minimum() {
+	let mut t = frame_system::GenesisConfig::<Test>::default().build_storage().unwrap();
+	crate::GenesisConfig::<Test, Instance2> { balances: vec![(1, 4)], ..Default::default() }
+		.assimilate_storage(&mut t)
+		.unwrap();
+}
+
+/// Should decrement the mint quota and revert once it is used up
+#[test]
+fn mint_quota_ok() {
+	ExtBuilder::default().build_and_execute(|| {
+		assert_ok!(ERC20Module::set_mint_quota(RuntimeOrigin::signed(10), 1, 50, None));
+		System::assert_last_event(
+			Event::<Test>::MintQuotaSet { minter: 1, allowance: 50, rate_limit: None }.into(),
+		);
+		assert_ok!(ERC20Module::mint(RuntimeOrigin::signed(1), 40));
+		assert_eq!(
+			ERC20Module::mint_quota(&1),
+			Some(MintQuota { remaining: 10, rate_limit: None })
+		);
+		assert_noop!(
+			ERC20Module::mint(RuntimeOrigin::signed(1), 11),
+			Error::<Test>::MintQuotaExceeded
+		);
+
+		// refill
+		assert_ok!(ERC20Module::set_mint_quota(RuntimeOrigin::root(), 1, 20, None));
+		assert_ok!(ERC20Module::mint(RuntimeOrigin::signed(1), 20));
+		assert_eq!(ERC20Module::balance_of(&1), 60);
+	});
+}
+
+/// Should limit the amount minted per period
+#[test]
+fn mint_rate_limit_ok() {
+	ExtBuilder::default().build_and_execute(|| {
+		assert_ok!(ERC20Module::set_mint_quota(RuntimeOrigin::signed(10), 1, 100, Some((30, 10))));
+		assert_ok!(ERC20Module::mint(RuntimeOrigin::signed(1), 20));
+		System::set_block_number(10);
+		assert_noop!(
+			ERC20Module::mint(RuntimeOrigin::signed(1), 20),
+			Error::<Test>::MintQuotaExceeded
+		);
+
+		System::set_block_number(11);
+		assert_ok!(ERC20Module::mint(RuntimeOrigin::signed(1), 20));
+		assert_eq!(
+			ERC20Module::mint_quota(&1),
+			Some(MintQuota {
+				remaining: 60,
+				rate_limit: Some(MintRateLimit {
+					max_per_period: 30,
+					period: 10,
+					period_start: 11,
+					minted: 20
+				}),
+			})
+		);
+	});
+}
+
+/// Should revert if the mint quota is set by a non admin or with a zero period
+#[test]
+fn set_mint_quota_bad_input() {
+	ExtBuilder::default().build_and_execute(|| {
+		assert_noop!(
+			ERC20Module::set_mint_quota(RuntimeOrigin::signed(1), 1, 50, None),
+			Error::<Test>::AccessControl(Role::Admin)
+		);
+		assert_noop!(
+			ERC20Module::set_mint_quota(RuntimeOrigin::signed(10), 1, 50, Some((10, 0))),
+			Error::<Test>::InvalidMintPeriod
+		);
+	});
+}
+
+/// Should lift the mint quota when cleared
+#[test]
+fn clear_mint_quota_ok() {
+	ExtBuilder::default().build_and_execute(|| {
+		assert_ok!(ERC20Module::set_mint_quota(RuntimeOrigin::signed(10), 1, 0, None));
+		assert_noop!(
+			ERC20Module::mint(RuntimeOrigin::signed(1), 1),
+			Error::<Test>::MintQuotaExceeded
+		);
+
+		assert_ok!(ERC20Module::clear_mint_quota(RuntimeOrigin::signed(10), 1));
+		System::assert_last_event(Event::<Test>::MintQuotaCleared { minter: 1 }.into());
+		assert_ok!(ERC20Module::mint(RuntimeOrigin::signed(1), 1));
+	});
+}
+
+/// Should increase and decrease the allowance
+#[test]
+fn change_allowance_ok() {
+	ExtBuilder::default().build_and_execute(|| {
+		assert_ok!(ERC20Module::approve(RuntimeOrigin::signed(1), 2, 10));
+		assert_ok!(ERC20Module::increase_allowance(RuntimeOrigin::signed(1), 2, 32));
+		assert_eq!(ERC20Module::allowances(&1, &2), 42);
+		System::assert_last_event(
+			Event::<Test>::Approval { owner: 1, spender: 2, value: 42 }.into(),
+		);
+
+		assert_ok!(ERC20Module::decrease_allowance(RuntimeOrigin::signed(1), 2, 40));
+		assert_eq!(ERC20Module::allowances(&1, &2), 2);
+	});
+}
+
+/// Should revert if the allowance would go below zero or overflow
+#[test]
+fn change_allowance_out_of_range() {
+	ExtBuilder::default().build_and_execute(|| {
+		assert_ok!(ERC20Module::approve(RuntimeOrigin::signed(1), 2, 10));
+		assert_noop!(
+			ERC20Module::decrease_allowance(RuntimeOrigin::signed(1), 2, 11),
+			Error::<Test>::AllowanceBelowZero
+		);
+		assert_noop!(
+			ERC20Module::increase_allowance(RuntimeOrigin::signed(1), 2, u64::MAX),
+			Error::<Test>::StorageOverflow
+		);
+	});
+}
+
+/// Should burn tokens of another account through the allowance
+#[test]
+fn burn_from_ok() {
+	ExtBuilder { balances: vec![(2, 42)], ..Default::default() }.build_and_execute(|| {
+		assert_ok!(ERC20Module::approve(RuntimeOrigin::signed(2), 1, 50));
+		assert_ok!(ERC20Module::burn_from(RuntimeOrigin::signed(1), 2, 40));
+		System::assert_last_event(Event::<Test>::Burned { from: 2, value: 40 }.into());
+		assert_eq!(ERC20Module::balance_of(&2), 2);
+		assert_eq!(ERC20Module::allowances(&2, &1), 10);
+		assert_eq!(ERC20Module::total_supply(), 2);
+	});
+}
+
+/// Should revert burn_from without allowance or burner role
+#[test]
+fn burn_from_bad_authority() {
+	ExtBuilder { balances: vec![(2, 42)], ..Default::default() }.build_and_execute(|| {
+		assert_ok!(ERC20Module::approve(RuntimeOrigin::signed(2), 1, 10));
+		assert_ok!(ERC20Module::approve(RuntimeOrigin::signed(2), 3, 10));
+		assert_noop!(
+			ERC20Module::burn_from(RuntimeOrigin::signed(1), 2, 11),
+			Error::<Test>::ERC20InsufficientAllowance
+		);
+		assert_noop!(
+			ERC20Module::burn_from(RuntimeOrigin::signed(3), 2, 10),
+			Error::<Test>::AccessControl(Role::Burner)
+		);
+	});
+}
+
+/// Should approve through a permit signed by the owner
+#[test]
+fn permit_ok() {
+	ExtBuilder::default().build_and_execute(|| {
+		let payload = ERC20Module::permit_payload(1, 2, 42, 5);
+		assert_eq!(payload.nonce, 0);
+		let signature = TestSignature(1, payload.encode());
+
+		assert_ok!(ERC20Module::permit(RuntimeOrigin::signed(3), 1, 2, 42, 5, signature.clone()));
+		assert_eq!(ERC20Module::allowances(&1, &2), 42);
+		assert_eq!(ERC20Module::permit_nonce(&1), 1);
+		System::assert_last_event(
+			Event::<Test>::Approval { owner: 1, spender: 2, value: 42 }.into(),
+		);
+
+		// the nonce was used
+		assert_noop!(
+			ERC20Module::permit(RuntimeOrigin::signed(3), 1, 2, 42, 5, signature),
+			Error::<Test>::InvalidPermitSignature
+		);
+	});
+}
+
+/// Should reserve the deposit of a permit from its submitter and release it to the submitter
+#[test]
+fn permit_deposit_paid_by_submitter() {
+	ExtBuilder { balances: vec![(6, 42)], ..Default::default() }.build_and_execute(|| {
+		// Account 6 holds no native balance and could not pay the deposit itself.
+		assert_eq!(NativeBalances::free_balance(&6), 0);
+		let payload = ERC20Module::permit_payload(6, 2, 42, 5);
+
+		assert_ok!(ERC20Module::permit(
+			RuntimeOrigin::signed(3),
+			6,
+			2,
+			42,
+			5,
+			TestSignature(6, payload.encode())
+		));
+		assert_eq!(ERC20Module::allowances(&6, &2), 42);
+		assert_eq!(AllowanceDeposits::<Test>::get(6, 2), Some((3, ALLOWANCE_DEPOSIT)));
+		assert_eq!(NativeBalances::reserved_balance(&3), ALLOWANCE_DEPOSIT);
+
+		assert_ok!(ERC20Module::transfer_from(RuntimeOrigin::signed(2), 6, 4, 42));
+		assert!(!AllowanceDeposits::<Test>::contains_key(6, 2));
+		assert_eq!(NativeBalances::reserved_balance(&3), 0);
+	});
+}
+
+/// Should revert a permit signed by another account, for another instance or after the deadline
+#[test]
+fn permit_bad_signature() {
+	ExtBuilder::default().build_and_execute(|| {
+		let payload = ERC20Module::permit_payload(1, 2, 42, 5).encode();
+		assert_noop!(
+			ERC20Module::permit(
+				RuntimeOrigin::signed(3),
+				1,
+				2,
+				42,
+				5,
+				TestSignature(2, payload.clone())
+			),
+			Error::<Test>::InvalidPermitSignature
+		);
+		assert_noop!(
+			SecondERC20Module::permit(
+				RuntimeOrigin::signed(3),
+				1,
+				2,
+				42,
+				5,
+				TestSignature(1, payload.clone())
+			),
+			Error::<Test, Instance2>::InvalidPermitSignature
+		);
+
+		System::set_block_number(6);
+		assert_noop!(
+			ERC20Module::permit(RuntimeOrigin::signed(3), 1, 2, 42, 5, TestSignature(1, payload)),
+			Error::<Test>::PermitExpired
+		);
+	});
+}
+
+/// Should transfer to every recipient of the batch
+#[test]
+fn batch_transfer_ok() {
+	ExtBuilder { balances: vec![(1, 42)], ..Default::default() }.build_and_execute(|| {
+		assert_ok!(ERC20Module::batch_transfer(RuntimeOrigin::signed(1), vec![(2, 10), (3, 20)]));
+		assert_eq!(ERC20Module::balance_of(&1), 12);
+		assert_eq!(ERC20Module::balance_of(&2), 10);
+		assert_eq!(ERC20Module::balance_of(&3), 20);
+		System::assert_has_event(Event::<Test>::Transfer { from: 1, to: 2, value: 10 }.into());
+		System::assert_has_event(Event::<Test>::Transfer { from: 1, to: 3, value: 20 }.into());
+		System::assert_last_event(
+			Event::<Test>::BatchTransferred { from: 1, count: 2, total: 30 }.into(),
+		);
+	});
+}
+
+/// Should apply no transfer of the batch if one fails or the batch is too large
+#[test]
+fn batch_transfer_all_or_nothing() {
+	ExtBuilder { balances: vec![(1, 42)], ..Default::default() }.build_and_execute(|| {
+		assert_noop!(
+			ERC20Module::batch_transfer(RuntimeOrigin::signed(1), vec![(2, 40), (3, 3)]),
+			Error::<Test>::ERC20InsufficientBalance
+		);
+		assert_noop!(
+			ERC20Module::batch_transfer(
+				RuntimeOrigin::signed(1),
+				vec![(2, 1), (3, 1), (4, 1), (5, 1)]
+			),
+			Error::<Test>::TooManyTransfers
+		);
+	});
+}
+
+/// Should report balances and consequences through fungible::Inspect
+#[test]
+fn fungible_inspect_ok() {
+	ExtBuilder { balances: vec![(1, 42), (2, 10)], ..Default::default() }.build_and_execute(|| {
+		assert_eq!(ERC20Module::total_issuance(), 52);
+		assert_eq!(ERC20Module::minimum_balance(), 0);
+		assert_eq!(ERC20Module::balance(&1), 42);
+		assert_eq!(ERC20Module::total_balance(&1), 42);
+		assert_eq!(
+			ERC20Module::reducible_balance(&1, Preservation::Preserve, Fortitude::Polite),
+			42
+		);
+
+		assert_eq!(
+			ERC20Module::can_deposit(&3, 10, Provenance::Extant),
+			DepositConsequence::Success
+		);
+		assert_eq!(
+			ERC20Module::can_deposit(&1, u64::MAX, Provenance::Extant),
+			DepositConsequence::Overflow
+		);
+		assert_eq!(ERC20Module::can_withdraw(&1, 42), WithdrawConsequence::Success);
+		assert_eq!(ERC20Module::can_withdraw(&1, 43), WithdrawConsequence::BalanceLow);
+		assert_eq!(ERC20Module::can_withdraw(&1, 53), WithdrawConsequence::Underflow);
+	});
+}
+
+/// Should refuse deposits above the supply cap and any change while paused
+#[test]
+fn fungible_inspect_cap_and_pause() {
+	ExtBuilder::default().build_and_execute(|| {
+		assert_eq!(
+			SecondERC20Module::can_deposit(&1, SECOND_MAX_SUPPLY + 1, Provenance::Minted),
+			DepositConsequence::Overflow
+		);
+		assert_eq!(
+			SecondERC20Module::can_deposit(&1, SECOND_MAX_SUPPLY + 1, Provenance::Extant),
+			DepositConsequence::Success
+		);
+
+		assert_ok!(ERC20Module::mint(RuntimeOrigin::signed(1), 42));
+		assert_ok!(ERC20Module::pause(RuntimeOrigin::root(), None));
+		assert_eq!(
+			ERC20Module::can_deposit(&2, 1, Provenance::Extant),
+			DepositConsequence::Blocked
+		);
+		assert_eq!(ERC20Module::can_withdraw(&1, 1), WithdrawConsequence::Frozen);
+		assert_eq!(
+			ERC20Module::reducible_balance(&1, Preservation::Expendable, Fortitude::Polite),
+			0
+		);
+		assert_noop!(ERC20Module::write_balance(&1, 0), Error::<Test>::TokenPaused);
+	});
+}
+
+/// Should follow the per-operation pause and the frozen accounts
+#[test]
+fn fungible_inspect_operation_pause_and_frozen() {
+	ExtBuilder { balances: vec![(1, 42)], ..Default::default() }.build_and_execute(|| {
+		assert_ok!(ERC20Module::pause(RuntimeOrigin::root(), Some(Operation::Transfer)));
+		assert_eq!(
+			ERC20Module::reducible_balance(&1, Preservation::Expendable, Fortitude::Polite),
+			0
+		);
+		assert_eq!(ERC20Module::can_withdraw(&1, 1), WithdrawConsequence::Frozen);
+		assert_eq!(
+			ERC20Module::can_deposit(&2, 1, Provenance::Extant),
+			DepositConsequence::Blocked
+		);
+		assert_eq!(
+			ERC20Module::can_deposit(&2, 1, Provenance::Minted),
+			DepositConsequence::Success
+		);
+		assert_ok!(ERC20Module::unpause(RuntimeOrigin::root(), Some(Operation::Transfer)));
+
+		assert_ok!(ERC20Module::pause(RuntimeOrigin::root(), Some(Operation::Mint)));
+		assert_eq!(
+			ERC20Module::can_deposit(&2, 1, Provenance::Minted),
+			DepositConsequence::Blocked
+		);
+		assert_eq!(
+			ERC20Module::can_deposit(&2, 1, Provenance::Extant),
+			DepositConsequence::Success
+		);
+		assert_ok!(ERC20Module::unpause(RuntimeOrigin::root(), Some(Operation::Mint)));
+
+		assert_ok!(ERC20Module::freeze_account(RuntimeOrigin::root(), 1));
+		assert_eq!(
+			ERC20Module::reducible_balance(&1, Preservation::Expendable, Fortitude::Polite),
+			0
+		);
+		assert_eq!(ERC20Module::can_withdraw(&1, 1), WithdrawConsequence::Frozen);
+		assert_eq!(
+			ERC20Module::can_deposit(&1, 1, Provenance::Extant),
+			DepositConsequence::Blocked
+		);
+	});
+}
+
+/// Should count the reserved balance towards the minimum balance
+#[test]
+fn fungible_inspect_held() {
+	ExtBuilder::default().build_and_execute(|| {
+		assert_ok!(SecondERC20Module::mint(RuntimeOrigin::signed(2), 10));
+		assert_ok!(SecondERC20Module::reserve(&2, 7));
+
+		// The reserved balance keeps the account alive, so all the free balance can be spent.
+		assert_eq!(
+			SecondERC20Module::reducible_balance(&2, Preservation::Preserve, Fortitude::Polite),
+			3
+		);
+		assert_eq!(SecondERC20Module::can_withdraw(&2, 3), WithdrawConsequence::Success);
+		assert_eq!(
+			SecondERC20Module::can_deposit(&2, 1, Provenance::Extant),
+			DepositConsequence::Success
+		);
+		assert_eq!(
+			SecondERC20Module::can_deposit(&3, 1, Provenance::Extant),
+			DepositConsequence::BelowMinimum
+		);
+	});
+}
+
+/// Should mint, burn and transfer through fungible::Mutate like the calls of the pallet
+#[test]
+fn fungible_mutate_ok() {
+	ExtBuilder::default().build_and_execute(|| {
+		assert_eq!(ERC20Module::mint_into(&1, 42), Ok(42));
+		assert_eq!(ERC20Module::total_supply(), 42);
+
+		assert_eq!(
+			<ERC20Module as Mutate<u64>>::transfer(&1, &2, 10, Preservation::Expendable),
+			Ok(10)
+		);
+		System::assert_last_event(Event::<Test>::Transfer { from: 1, to: 2, value: 10 }.into());
+
+		assert_eq!(
+			<ERC20Module as Mutate<u64>>::burn_from(
+				&2,
+				20,
+				Precision::BestEffort,
+				Fortitude::Polite
+			),
+			Ok(10)
+		);
+		System::assert_last_event(Event::<Test>::Burned { from: 2, value: 10 }.into());
+		assert_noop!(
+			<ERC20Module as Mutate<u64>>::burn_from(&1, 40, Precision::Exact, Fortitude::Polite),
+			Error::<Test>::ERC20InsufficientBalance
+		);
+		assert_eq!(ERC20Module::balance_of(&1), 32);
+		assert_eq!(ERC20Module::total_supply(), 32);
+
+		assert_noop!(
+			SecondERC20Module::mint_into(&1, SECOND_MAX_SUPPLY + 1),
+			Error::<Test, Instance2>::SupplyCapExceeded
+		);
+		assert_ok!(ERC20Module::pause(RuntimeOrigin::root(), Some(Operation::Mint)));
+		assert_noop!(ERC20Module::mint_into(&1, 1), Error::<Test>::TokenPaused);
+	});
+}
+
+/// Should change balances without the total supply through fungible::Unbalanced
+#[test]
+fn fungible_unbalanced_ok() {
+	ExtBuilder::default().build_and_execute(|| {
+		assert_eq!(ERC20Module::write_balance(&1, 5), Ok(None));
+		assert_eq!(ERC20Module::balance_of(&1), 5);
+		assert_eq!(ERC20Module::total_supply(), 0);
+		ERC20Module::set_total_issuance(5);
+		assert_eq!(ERC20Module::total_supply(), 5);
+
+		assert_eq!(ERC20Module::increase_balance(&1, 5, Precision::Exact), Ok(5));
+		assert_eq!(
+			ERC20Module::decrease_balance(
+				&1,
+				20,
+				Precision::BestEffort,
+				Preservation::Expendable,
+				Fortitude::Polite
+			),
+			Ok(10)
+		);
+		assert_eq!(
+			ERC20Module::decrease_balance(
+				&1,
+				1,
+				Precision::Exact,
+				Preservation::Expendable,
+				Fortitude::Polite
+			),
+			Err(TokenError::FundsUnavailable.into())
+		);
+	});
+}
+
+/// Should keep the total supply in line with the imbalances of fungible::Balanced
+#[test]
+fn fungible_balanced_ok() {
+	ExtBuilder { balances: vec![(1, 42)], ..Default::default() }.build_and_execute(|| {
+		let credit = ERC20Module::withdraw(
+			&1,
+			10,
+			Precision::Exact,
+			Preservation::Expendable,
+			Fortitude::Polite,
+		)
+		.unwrap();
+		assert_eq!(credit.peek(), 10);
+		assert_eq!(ERC20Module::balance_of(&1), 32);
+		assert!(ERC20Module::resolve(&2, credit).is_ok());
+		assert_eq!(ERC20Module::balance_of(&2), 10);
+		assert_eq!(ERC20Module::total_supply(), 42);
+
+		// a dropped debt mints, a dropped credit burns
+		drop(ERC20Module::deposit(&3, 5, Precision::Exact).unwrap());
+		assert_eq!(ERC20Module::balance_of(&3), 5);
+		assert_eq!(ERC20Module::total_supply(), 47);
+		drop(
+			ERC20Module::withdraw(
+				&3,
+				5,
+				Precision::Exact,
+				Preservation::Expendable,
+				Fortitude::Polite,
+			)
+			.unwrap(),
+		);
+		assert_eq!(ERC20Module::total_supply(), 42);
+
+		let credit = ERC20Module::issue(8);
+		assert_eq!(ERC20Module::total_supply(), 50);
+		drop(credit);
+		assert_eq!(ERC20Module::total_supply(), 42);
+	});
+}
+
+/// Should issue through fungible::Balanced past the supply cap and while minting is paused
+#[test]
+fn fungible_balanced_uncapped() {
+	ExtBuilder::default().build_and_execute(|| {
+		assert_ok!(ERC20Module::pause(RuntimeOrigin::root(), Some(Operation::Mint)));
+		let supply = ERC20Module::total_supply();
+		drop(ERC20Module::deposit(&3, 5, Precision::Exact).unwrap());
+		assert_eq!(ERC20Module::balance_of(&3), 5);
+		assert_eq!(ERC20Module::total_supply(), supply + 5);
+		let credit = ERC20Module::issue(8);
+		assert_eq!(credit.peek(), 8);
+		assert_eq!(ERC20Module::total_supply(), supply + 13);
+		drop(credit);
+
+		drop(SecondERC20Module::deposit(&3, SECOND_MAX_SUPPLY + 1, Precision::Exact).unwrap());
+		assert!(SecondERC20Module::total_supply() > SECOND_MAX_SUPPLY);
+		assert_eq!(SecondERC20Module::remaining_mintable(), 0);
+	});
+}
+
+/// Should charge transaction fees in the token, refund the unused part and pay the collector
+#[test]
+fn transaction_fee_ok() {
+	ExtBuilder { balances: vec![(1, 100)], ..Default::default() }.build_and_execute(|| {
+		let call = RuntimeCall::System(frame_system::Call::remark { remark: vec![] });
+		let info = DispatchInfo::default();
+
+		// the mock charges 2 tokens per unit of native fee
+		let paid = TokenFeeAdapter::<Test>::withdraw_fee(&1, &call, &info, 10, 0).unwrap();
+		assert_eq!(ERC20Module::balance_of(&1), 80);
+		assert_ok!(TokenFeeAdapter::<Test>::correct_and_deposit_fee(
+			&1,
+			&info,
+			&PostDispatchInfo::default(),
+			6,
+			0,
+			paid
+		));
+		assert_eq!(ERC20Module::balance_of(&1), 88);
+		assert_eq!(ERC20Module::balance_of(&FEE_COLLECTOR), 12);
+		assert_eq!(ERC20Module::total_supply(), 100);
+		System::assert_last_event(Event::<Test>::TransactionFeePaid { who: 1, fee: 12 }.into());
+
+		assert_eq!(
+			TokenFeeAdapter::<Test>::withdraw_fee(&1, &call, &info, 45, 0).err(),
+			Some(InvalidTransaction::Payment.into())
+		);
+	});
+}
+
+/// Should convert fees at the rate set by the admin origin
+#[test]
+fn set_fee_rate_ok() {
+	ExtBuilder::default().build_and_execute(|| {
+		assert_eq!(ERC20Module::fee_rate(), FixedU128::from_u32(2));
+		assert_noop!(
+			ERC20Module::set_fee_rate(RuntimeOrigin::signed(10), FixedU128::from_u32(1)),
+			DispatchError::BadOrigin
+		);
+
+		assert_ok!(ERC20Module::set_fee_rate(
+			RuntimeOrigin::root(),
+			FixedU128::from_rational(1, 2)
+		));
+		System::assert_last_event(
+			Event::<Test>::FeeRateSet { rate: FixedU128::from_rational(1, 2) }.into(),
+		);
+		assert_eq!(ERC20Module::convert_fee(10), 5);
+	});
+}
+
+/// Should remove accounts falling below the minimum balance and burn their dust
+#[test]
+fn dust_account_reaped() {
+	ExtBuilder::default().build_and_execute(|| {
+		assert_ok!(SecondERC20Module::mint(RuntimeOrigin::signed(2), 10));
+		assert_ok!(SecondERC20Module::transfer(RuntimeOrigin::signed(2), 3, 7));
+		System::assert_last_event(
+			Event::<Test, Instance2>::DustLost { account: 2, amount: 3 }.into(),
+		);
+		assert!(!Balances::<Test, Instance2>::contains_key(2));
+		assert_eq!(SecondERC20Module::balance_of(&3), 7);
+		assert_eq!(SecondERC20Module::total_supply(), 7);
+
+		assert_ok!(SecondERC20Module::transfer(RuntimeOrigin::signed(3), 4, 7));
+		assert!(!Balances::<Test, Instance2>::contains_key(3));
+		assert_eq!(SecondERC20Module::total_supply(), 7);
+	});
+}
+
+/// Should refuse balances below the minimum balance
+#[test]
+fn below_minimum_balance() {
+	ExtBuilder::default().build_and_execute(|| {
+		assert_noop!(
+			SecondERC20Module::mint(RuntimeOrigin::signed(2), 4),
+			Error::<Test, Instance2>::BelowMinimumBalance
+		);
+		assert_ok!(SecondERC20Module::mint(RuntimeOrigin::signed(2), 10));
+		assert_noop!(
+			SecondERC20Module::transfer(RuntimeOrigin::signed(2), 3, 4),
+			Error::<Test, Instance2>::BelowMinimumBalance
+		);
+		assert_noop!(
+			SecondERC20Module::transfer_keep_alive(RuntimeOrigin::signed(2), 3, 6),
+			Error::<Test, Instance2>::WouldReapAccount
+		);
+		assert_ok!(SecondERC20Module::transfer_keep_alive(RuntimeOrigin::signed(2), 3, 5));
+		assert_eq!(SecondERC20Module::balance_of(&2), 5);
+	});
+}
+
+/// Should remove the accounts left below the minimum balance and burn their dust
+#[test]
+fn migration_v4_reaps_dust_accounts() {
+	ExtBuilder::default().build_and_execute(|| {
+		Balances::<Test, Instance2>::insert(1, 3);
+		Balances::<Test, Instance2>::insert(2, 0);
+		Balances::<Test, Instance2>::insert(3, 10);
+		TotalSupply::<Test, Instance2>::put(13);
+		Locked::<Test, Instance2>::insert(1, 3);
+		StorageVersion::new(3).put::<SecondERC20Module>();
+
+		migrations::v4::ReapDustAccounts::<Test, Instance2>::on_runtime_upgrade();
+
+		assert!(!Balances::<Test, Instance2>::contains_key(1));
+		assert!(!Locked::<Test, Instance2>::contains_key(1));
+		assert!(!Balances::<Test, Instance2>::contains_key(2));
+		assert_eq!(SecondERC20Module::balance_of(&3), 10);
+		assert_eq!(SecondERC20Module::total_supply(), 10);
+		assert_eq!(SecondERC20Module::on_chain_storage_version(), 4);
+	});
+}
+
+/// Should reserve a deposit for a new allowance and release it once the allowance is used up
+#[test]
+fn allowance_deposit_ok() {
+	ExtBuilder::default().build_and_execute(|| {
+		assert_ok!(ERC20Module::mint(RuntimeOrigin::signed(1), 42));
+		assert_ok!(ERC20Module::approve(RuntimeOrigin::signed(1), 2, 40));
+		assert_eq!(NativeBalances::reserved_balance(&1), ALLOWANCE_DEPOSIT);
+		assert_ok!(ERC20Module::increase_allowance(RuntimeOrigin::signed(1), 2, 2));
+		assert_eq!(NativeBalances::reserved_balance(&1), ALLOWANCE_DEPOSIT);
+
+		assert_ok!(ERC20Module::transfer_from(RuntimeOrigin::signed(2), 1, 3, 42));
+		assert!(!Allowances::<Test>::contains_key(1, 2));
+		assert_eq!(NativeBalances::reserved_balance(&1), 0);
+
+		assert_ok!(ERC20Module::approve(RuntimeOrigin::signed(1), 2, 10));
+		assert_ok!(ERC20Module::approve(RuntimeOrigin::signed(1), 2, 0));
+		assert!(!Allowances::<Test>::contains_key(1, 2));
+		assert_eq!(NativeBalances::reserved_balance(&1), 0);
+	});
+}
+
+/// Should refuse a new allowance if the owner cannot pay the deposit
+#[test]
+fn allowance_deposit_insufficient() {
+	ExtBuilder::default().build_and_execute(|| {
+		assert_noop!(
+			ERC20Module::approve(RuntimeOrigin::signed(6), 2, 42),
+			pallet_balances::Error::<Test>::InsufficientBalance
+		);
+		// The second instance asks for no deposit.
+		assert_ok!(SecondERC20Module::approve(RuntimeOrigin::signed(6), 2, 42));
+	});
+}
+
+/// Should remove an allowance and release its deposit
+#[test]
+fn remove_allowance_ok() {
+	ExtBuilder::default().build_and_execute(|| {
+		assert_ok!(ERC20Module::approve(RuntimeOrigin::signed(1), 2, 42));
+		assert_ok!(ERC20Module::remove_allowance(RuntimeOrigin::signed(1), 2));
+		System::assert_last_event(
+			Event::<Test>::Approval { owner: 1, spender: 2, value: 0 }.into(),
+		);
+		assert!(!Allowances::<Test>::contains_key(1, 2));
+		assert_eq!(NativeBalances::reserved_balance(&1), 0);
+
+		assert_noop!(
+			ERC20Module::remove_allowance(RuntimeOrigin::signed(1), 2),
+			Error::<Test>::NoAllowance
+		);
+	});
+}
+
+/// Should remove zero allowances and reserve deposits for the others where possible
+#[test]
+fn migration_v5_reserves_allowance_deposits() {
+	ExtBuilder::default().build_and_execute(|| {
+		Allowances::<Test>::insert(1, 2, 0);
+		Allowances::<Test>::insert(1, 3, 5);
+		Allowances::<Test>::insert(6, 2, 5);
+		StorageVersion::new(4).put::<ERC20Module>();
+
+		migrations::v5::ReserveAllowanceDeposits::<Test>::on_runtime_upgrade();
+
+		assert!(!Allowances::<Test>::contains_key(1, 2));
+		assert_eq!(AllowanceDeposits::<Test>::get(1, 3), Some((1, ALLOWANCE_DEPOSIT)));
+		assert_eq!(NativeBalances::reserved_balance(&1), ALLOWANCE_DEPOSIT);
+		// Account 6 cannot pay the deposit and keeps its allowance without one.
+		assert_eq!(ERC20Module::allowances(&6, &2), 5);
+		assert_eq!(AllowanceDeposits::<Test>::get(6, 2), None);
+		assert_eq!(ERC20Module::on_chain_storage_version(), 5);
+	});
+}
+
+/// Should move tokens between the free and reserved balances
+#[test]
+fn reserve_ok() {
+	ExtBuilder::default().build_and_execute(|| {
+		assert_ok!(ERC20Module::mint(RuntimeOrigin::signed(1), 42));
+		assert_ok!(ERC20Module::reserve(&1, 30));
+		System::assert_last_event(Event::<Test>::Reserved { who: 1, value: 30 }.into());
+		assert_eq!(ERC20Module::balance_of(&1), 12);
+		assert_eq!(ERC20Module::reserved_balance(&1), 30);
+		assert_eq!(ERC20Module::total_balance_of(&1), 42);
+		assert_eq!(ERC20Module::total_supply(), 42);
+
+		assert_noop!(
+			ERC20Module::transfer(RuntimeOrigin::signed(1), 2, 13),
+			Error::<Test>::ERC20InsufficientBalance
+		);
+		assert_noop!(ERC20Module::reserve(&1, 13), Error::<Test>::ERC20InsufficientBalance);
+
+		assert_eq!(ERC20Module::unreserve(&1, 40), 10);
+		System::assert_last_event(Event::<Test>::Unreserved { who: 1, value: 30 }.into());
+		assert_eq!(ERC20Module::balance_of(&1), 42);
+		assert_eq!(ERC20Module::reserved_balance(&1), 0);
+	});
+}
+
+/// Should move reserved tokens to the free or reserved balance of another account
+#[test]
+fn repatriate_reserved_ok() {
+	ExtBuilder::default().build_and_execute(|| {
+		assert_ok!(ERC20Module::mint(RuntimeOrigin::signed(1), 42));
+		assert_ok!(ERC20Module::reserve(&1, 30));
+
+		assert_eq!(ERC20Module::repatriate_reserved(&1, &2, 10, BalanceStatus::Free), Ok(0));
+		System::assert_last_event(
+			Event::<Test>::ReserveRepatriated {
+				from: 1,
+				to: 2,
+				value: 10,
+				destination_status: BalanceStatus::Free,
+			}
+			.into(),
+		);
+		assert_eq!(ERC20Module::balance_of(&2), 10);
+
+		assert_eq!(ERC20Module::repatriate_reserved(&1, &2, 25, BalanceStatus::Reserved), Ok(5));
+		assert_eq!(ERC20Module::reserved_balance(&1), 0);
+		assert_eq!(ERC20Module::reserved_balance(&2), 20);
+		assert_eq!(ERC20Module::balance_of(&1), 12);
+		assert_eq!(ERC20Module::total_supply(), 42);
+	});
+}
+
+/// Should keep an account with reserved tokens and burn its dust once they are gone
+#[test]
+fn reserve_keeps_account_alive() {
+	ExtBuilder::default().build_and_execute(|| {
+		assert_ok!(SecondERC20Module::mint(RuntimeOrigin::signed(2), 10));
+		assert_ok!(SecondERC20Module::reserve(&2, 8));
+		assert_eq!(SecondERC20Module::balance_of(&2), 2);
+
+		assert_eq!(SecondERC20Module::repatriate_reserved(&2, &3, 8, BalanceStatus::Free), Ok(0));
+		System::assert_last_event(
+			Event::<Test, Instance2>::DustLost { account: 2, amount: 2 }.into(),
+		);
+		assert!(!Balances::<Test, Instance2>::contains_key(2));
+		assert_eq!(SecondERC20Module::balance_of(&3), 8);
+		assert_eq!(SecondERC20Module::total_supply(), 8);
+	});
+}
+
+/// Should put tokens on hold for a reason, out of reach of `unreserve`
+#[test]
+fn hold_ok() {
+	ExtBuilder::default().build_and_execute(|| {
+		assert_ok!(ERC20Module::mint(RuntimeOrigin::signed(1), 42));
+		assert_ok!(ERC20Module::hold(&HoldReason::Escrow, &1, 20));
+		assert_ok!(ERC20Module::hold(&HoldReason::Bond, &1, 2));
+		assert_eq!(ERC20Module::balance_on_hold(&HoldReason::Escrow, &1), 20);
+		assert_eq!(ERC20Module::total_balance_on_hold(&1), 22);
+		assert_eq!(ERC20Module::reserved_balance(&1), 22);
+		assert_eq!(ERC20Module::balance_of(&1), 20);
+
+		assert_eq!(ERC20Module::unreserve(&1, 20), 20);
+		assert_ok!(ERC20Module::release(&HoldReason::Escrow, &1, 20, Precision::Exact));
+		assert_eq!(ERC20Module::balance_on_hold(&HoldReason::Escrow, &1), 0);
+		assert_eq!(ERC20Module::reserved_balance(&1), 2);
+		assert_eq!(ERC20Module::balance_of(&1), 40);
+	});
+}
+
+/// Should lock vested transfers and unlock them linearly after the cliff
+#[test]
+fn vested_transfer_ok() {
+	ExtBuilder::default().build_and_execute(|| {
+		let schedule = VestingSchedule { locked: 50, per_block: 5, start: 1, cliff: 4 };
+		assert_ok!(ERC20Module::mint(RuntimeOrigin::signed(1), 100));
+		assert_ok!(ERC20Module::vested_transfer(RuntimeOrigin::signed(1), 2, schedule));
+		System::assert_last_event(
+			Event::<Test>::VestingScheduleAdded { account: 2, schedule }.into(),
+		);
+		assert_eq!(ERC20Module::balance_of(&2), 50);
+		assert_eq!(ERC20Module::locked(&2), 50);
+		assert_noop!(
+			ERC20Module::transfer(RuntimeOrigin::signed(2), 3, 1),
+			Error::<Test>::BalanceLocked
+		);
+
+		// Nothing unlocks before the cliff.
+		System::set_block_number(4);
+		assert_ok!(ERC20Module::vest(RuntimeOrigin::signed(2)));
+		System::assert_last_event(
+			Event::<Test>::VestingUpdated { account: 2, unvested: 50 }.into(),
+		);
+
+		System::set_block_number(5);
+		assert_ok!(ERC20Module::vest(RuntimeOrigin::signed(2)));
+		assert_eq!(ERC20Module::locked(&2), 30);
+		assert_ok!(ERC20Module::transfer(RuntimeOrigin::signed(2), 3, 20));
+		assert_noop!(
+			ERC20Module::transfer(RuntimeOrigin::signed(2), 3, 1),
+			Error::<Test>::BalanceLocked
+		);
+
+		System::set_block_number(11);
+		assert_ok!(ERC20Module::vest(RuntimeOrigin::signed(2)));
+		System::assert_last_event(Event::<Test>::VestingCompleted { account: 2 }.into());
+		assert_eq!(ERC20Module::locked(&2), 0);
+		assert!(ERC20Module::vesting(&2).is_empty());
+		assert_ok!(ERC20Module::transfer(RuntimeOrigin::signed(2), 3, 30));
+		assert_noop!(ERC20Module::vest(RuntimeOrigin::signed(2)), Error::<Test>::NotVesting);
+	});
+}
+
+/// Should remove the vesting schedules of a reaped account
+#[test]
+fn vesting_reaped_account() {
+	ExtBuilder::default().build_and_execute(|| {
+		let schedule = VestingSchedule { locked: 10, per_block: 1, start: 1, cliff: 0 };
+		assert_ok!(SecondERC20Module::mint(RuntimeOrigin::signed(2), 100));
+		assert_ok!(SecondERC20Module::vested_transfer(RuntimeOrigin::signed(2), 4, schedule));
+
+		System::set_block_number(8);
+		assert_ok!(SecondERC20Module::vest(RuntimeOrigin::signed(4)));
+		assert_eq!(SecondERC20Module::locked(&4), 3);
+		// The locked tokens are left below the minimum balance and burned as dust.
+		assert_ok!(SecondERC20Module::transfer(RuntimeOrigin::signed(4), 5, 7));
+		assert!(!Balances::<Test, Instance2>::contains_key(4));
+		assert_eq!(SecondERC20Module::locked(&4), 0);
+		assert!(SecondERC20Module::vesting(&4).is_empty());
+	});
+}
+
+/// Should refuse vested transfers from other accounts than minters and admins, and bad schedules
+#[test]
+fn vested_transfer_bad_input() {
+	ExtBuilder { balances: vec![(1, 100), (3, 100)], ..Default::default() }.build_and_execute(
+		|| {
+			let schedule = VestingSchedule { locked: 10, per_block: 1, start: 1, cliff: 0 };
+			assert_noop!(
+				ERC20Module::vested_transfer(RuntimeOrigin::signed(3), 2, schedule),
+				Error::<Test>::AccessControl(Role::Minter)
+			);
+			assert_noop!(
+				ERC20Module::vested_transfer(
+					RuntimeOrigin::signed(1),
+					2,
+					VestingSchedule { per_block: 0, ..schedule }
+				),
+				Error::<Test>::InvalidVestingSchedule
+			);
+
+			for _ in 0..3 {
+				assert_ok!(ERC20Module::vested_transfer(RuntimeOrigin::signed(1), 2, schedule));
+			}
+			assert_noop!(
+				ERC20Module::vested_transfer(RuntimeOrigin::signed(1), 2, schedule),
+				Error::<Test>::TooManyVestingSchedules
+			);
+		},
+	);
+}
+
+/// Should lock genesis balances with the genesis vesting schedules
+#[test]
+fn genesis_vesting_ok() {
+	let mut t = frame_system::GenesisConfig::<Test>::default().build_storage().unwrap();
+	crate::GenesisConfig::<Test> {
+		balances: vec![(1, 100)],
+		vesting: vec![(1, 0, 10, 1, 60)],
+		..Default::default()
+	}
+	.assimilate_storage(&mut t)
+	.unwrap();
+
+	sp_io::TestExternalities::new(t).execute_with(|| {
+		System::set_block_number(1);
+		assert_eq!(ERC20Module::locked(&1), 60);
+		assert_noop!(
+			ERC20Module::transfer(RuntimeOrigin::signed(1), 2, 41),
+			Error::<Test>::BalanceLocked
+		);
+		assert_ok!(ERC20Module::transfer(RuntimeOrigin::signed(1), 2, 40));
+	});
+}
+
+/// Should keep the balances of past snapshots
+#[test]
+fn snapshot_ok() {
+	ExtBuilder::default().build_and_execute(|| {
+		assert_ok!(ERC20Module::mint(RuntimeOrigin::signed(1), 100));
+		assert_noop!(ERC20Module::snapshot(RuntimeOrigin::signed(10)), DispatchError::BadOrigin);
+		assert_eq!(ERC20Module::balance_of_at(&1, 0), Err(Error::<Test>::NonexistentSnapshot));
+		assert_eq!(ERC20Module::balance_of_at(&1, 1), Err(Error::<Test>::NonexistentSnapshot));
+
+		assert_ok!(ERC20Module::snapshot(RuntimeOrigin::root()));
+		System::assert_last_event(Event::<Test>::Snapshot { id: 1 }.into());
+		assert_eq!(ERC20Module::balance_of_at(&1, 1), Ok(100));
+		assert_eq!(ERC20Module::balance_of_at(&2, 1), Ok(0));
+
+		assert_ok!(ERC20Module::transfer(RuntimeOrigin::signed(1), 2, 30));
+		assert_ok!(ERC20Module::transfer(RuntimeOrigin::signed(1), 2, 10));
+		assert_ok!(ERC20Module::snapshot(RuntimeOrigin::root()));
+		assert_ok!(ERC20Module::snapshot(RuntimeOrigin::root()));
+		assert_ok!(ERC20Module::transfer(RuntimeOrigin::signed(2), 3, 40));
+
+		assert_eq!(ERC20Module::current_snapshot_id(), 3);
+		assert_eq!(ERC20Module::balance_of_at(&1, 1), Ok(100));
+		assert_eq!(ERC20Module::balance_of_at(&2, 1), Ok(0));
+		assert_eq!(ERC20Module::balance_of_at(&1, 2), Ok(60));
+		assert_eq!(ERC20Module::balance_of_at(&2, 2), Ok(40));
+		assert_eq!(ERC20Module::balance_of_at(&2, 3), Ok(40));
+		assert_eq!(ERC20Module::balance_of_at(&3, 3), Ok(0));
+		assert_eq!(ERC20Module::balance_of(&2), 0);
+		assert_eq!(ERC20Module::balance_of_at(&1, 4), Err(Error::<Test>::NonexistentSnapshot));
+	});
+}
+
+/// Should find the balances of past snapshots among many checkpoints
+#[test]
+fn snapshot_checkpoints_ok() {
+	ExtBuilder::default().build_and_execute(|| {
+		assert_ok!(ERC20Module::mint(RuntimeOrigin::signed(1), 100));
+		for id in 1..=8 {
+			assert_ok!(ERC20Module::snapshot(RuntimeOrigin::root()));
+			if id % 3 != 0 {
+				assert_ok!(ERC20Module::transfer(RuntimeOrigin::signed(1), 2, 10));
+			}
+		}
+
+		assert_eq!(NumAccountSnapshots::<Test>::get(1), 6);
+		let balances = [100, 90, 80, 80, 70, 60, 60, 50];
+		for (id, balance) in (1..=8).zip(balances) {
+			assert_eq!(ERC20Module::balance_of_at(&1, id), Ok(balance));
+			assert_eq!(ERC20Module::balance_of_at(&2, id), Ok(100 - balance));
+		}
+		assert_eq!(ERC20Module::balance_of(&1), 40);
+	});
+}
+
+/// Should keep the total supply of past snapshots
+#[test]
+fn total_supply_at_ok() {
+	ExtBuilder::default().build_and_execute(|| {
+		assert_ok!(ERC20Module::mint(RuntimeOrigin::signed(1), 100));
+		assert_ok!(ERC20Module::snapshot(RuntimeOrigin::root()));
+		assert_ok!(ERC20Module::mint(RuntimeOrigin::signed(1), 50));
+		assert_ok!(ERC20Module::snapshot(RuntimeOrigin::root()));
+		assert_ok!(ERC20Module::burn(RuntimeOrigin::signed(1), 120));
+
+		assert_eq!(ERC20Module::total_supply_at(1), Ok(100));
+		assert_eq!(ERC20Module::total_supply_at(2), Ok(150));
+		assert_eq!(ERC20Module::balance_of_at(&1, 2), Ok(150));
+		assert_eq!(ERC20Module::total_supply(), 30);
+		assert_eq!(ERC20Module::total_supply_at(0), Err(Error::<Test>::NonexistentSnapshot));
+	});
+}
+
+/// Should move voting power with delegations and balance changes
+#[test]
+fn delegate_ok() {
+	ExtBuilder::default().build_and_execute(|| {
+		assert_ok!(ERC20Module::mint(RuntimeOrigin::signed(1), 100));
+		assert_ok!(ERC20Module::delegate(RuntimeOrigin::signed(1), 1));
+		System::assert_has_event(
+			Event::<Test>::DelegateChanged { delegator: 1, from_delegate: None, to_delegate: 1 }
+				.into(),
+		);
+		System::assert_last_event(
+			Event::<Test>::DelegateVotesChanged { delegate: 1, previous_votes: 0, new_votes: 100 }
+				.into(),
+		);
+		assert_eq!(ERC20Module::delegates(1), Some(1));
+		assert_eq!(ERC20Module::get_votes(&1), 100);
+
+		// Account 2 has not delegated, so what it receives has no voting power.
+		assert_ok!(ERC20Module::transfer(RuntimeOrigin::signed(1), 2, 30));
+		assert_eq!(ERC20Module::get_votes(&1), 70);
+		assert_eq!(ERC20Module::get_votes(&2), 0);
+
+		assert_ok!(ERC20Module::delegate(RuntimeOrigin::signed(2), 3));
+		assert_eq!(ERC20Module::get_votes(&3), 30);
+		assert_ok!(ERC20Module::delegate(RuntimeOrigin::signed(1), 3));
+		System::assert_has_event(
+			Event::<Test>::DelegateChanged { delegator: 1, from_delegate: Some(1), to_delegate: 3 }
+				.into(),
+		);
+		assert_eq!(ERC20Module::get_votes(&1), 0);
+		assert_eq!(ERC20Module::get_votes(&3), 100);
+
+		assert_ok!(ERC20Module::transfer(RuntimeOrigin::signed(1), 2, 20));
+		assert_eq!(ERC20Module::get_votes(&3), 100);
+		assert_ok!(ERC20Module::burn(RuntimeOrigin::signed(1), 50));
+		assert_eq!(ERC20Module::get_votes(&3), 50);
+	});
+}
+
+/// Should count the reserved balance in the snapshots and the voting power
+#[test]
+fn reserved_balance_votes_and_snapshots() {
+	ExtBuilder::default().build_and_execute(|| {
+		assert_ok!(ERC20Module::mint(RuntimeOrigin::signed(1), 100));
+		assert_ok!(ERC20Module::reserve(&1, 30));
+		assert_ok!(ERC20Module::delegate(RuntimeOrigin::signed(1), 1));
+		assert_eq!(ERC20Module::get_votes(&1), 100);
+
+		assert_ok!(ERC20Module::snapshot(RuntimeOrigin::root()));
+		assert_ok!(ERC20Module::reserve(&1, 20));
+		assert_eq!(ERC20Module::unreserve(&1, 10), 0);
+		assert_eq!(ERC20Module::get_votes(&1), 100);
+		assert_eq!(ERC20Module::balance_of_at(&1, 1), Ok(100));
+		assert_eq!(ERC20Module::total_supply_at(1), Ok(100));
+
+		// Reserved tokens moved to another account take their voting power along.
+		assert_eq!(ERC20Module::repatriate_reserved(&1, &2, 15, BalanceStatus::Reserved), Ok(0));
+		assert_eq!(ERC20Module::get_votes(&1), 85);
+		assert_ok!(ERC20Module::snapshot(RuntimeOrigin::root()));
+		assert_eq!(ERC20Module::balance_of_at(&1, 2), Ok(85));
+		assert_eq!(ERC20Module::balance_of_at(&2, 2), Ok(15));
+	});
+}
+
+/// Should keep the votes and total supply of past blocks
+#[test]
+fn get_past_votes_ok() {
+	ExtBuilder::default().build_and_execute(|| {
+		assert_ok!(ERC20Module::delegate(RuntimeOrigin::signed(1), 1));
+		assert_ok!(ERC20Module::mint(RuntimeOrigin::signed(1), 100));
+		System::set_block_number(3);
+		assert_ok!(ERC20Module::transfer(RuntimeOrigin::signed(1), 2, 40));
+		assert_ok!(ERC20Module::transfer(RuntimeOrigin::signed(1), 2, 10));
+		System::set_block_number(6);
+		assert_ok!(ERC20Module::mint(RuntimeOrigin::signed(1), 25));
+		System::set_block_number(10);
+
+		assert_eq!(ERC20Module::get_past_votes(&1, 0), Ok(0));
+		assert_eq!(ERC20Module::get_past_votes(&1, 1), Ok(100));
+		assert_eq!(ERC20Module::get_past_votes(&1, 2), Ok(100));
+		assert_eq!(ERC20Module::get_past_votes(&1, 3), Ok(50));
+		assert_eq!(ERC20Module::get_past_votes(&1, 9), Ok(75));
+		assert_eq!(ERC20Module::get_past_total_supply(0), Ok(0));
+		assert_eq!(ERC20Module::get_past_total_supply(5), Ok(100));
+		assert_eq!(ERC20Module::get_past_total_supply(6), Ok(125));
+		assert_eq!(ERC20Module::get_past_votes(&1, 10), Err(Error::<Test>::FutureLookup));
+		assert_eq!(ERC20Module::get_past_total_supply(11), Err(Error::<Test>::FutureLookup));
+	});
+}
+
+/// Should checkpoint the total supply on upgrade
+#[test]
+fn migration_v6_checkpoints_total_supply() {
+	ExtBuilder::default().build_and_execute(|| {
+		TotalSupply::<Test>::put(42);
+		StorageVersion::new(5).put::<ERC20Module>();
+		System::set_block_number(4);
+
+		migrations::v6::CheckpointTotalSupply::<Test>::on_runtime_upgrade();
+
+		System::set_block_number(5);
+		assert_eq!(ERC20Module::get_past_total_supply(3), Ok(0));
+		assert_eq!(ERC20Module::get_past_total_supply(4), Ok(42));
+		assert_eq!(ERC20Module::on_chain_storage_version(), 6);
+	});
+}
+
+/// Should lend minted tokens for the duration of a call and take them back with the fee
+#[test]
+fn flash_mint_ok() {
+	ExtBuilder { balances: vec![(4, 10)], ..Default::default() }.build_and_execute(|| {
+		let remark = Box::new(RuntimeCall::System(frame_system::Call::remark { remark: vec![] }));
+		assert_eq!(ERC20Module::max_flash_mint(), 1000);
+		assert_noop!(
+			ERC20Module::flash_mint(RuntimeOrigin::signed(4), 1001, remark.clone()),
+			Error::<Test>::ExceededMaxFlashMint
+		);
+
+		assert_ok!(ERC20Module::flash_mint(RuntimeOrigin::signed(4), 500, remark));
+		System::assert_last_event(
+			Event::<Test>::FlashMinted { borrower: 4, amount: 500, fee: 5 }.into(),
+		);
+		assert_eq!(ERC20Module::balance_of(&4), 5);
+		assert_eq!(ERC20Module::balance_of(&FEE_COLLECTOR), 5);
+		assert_eq!(ERC20Module::total_supply(), 10);
+	});
+}
+
+/// Should revert the flash mint and the call if the loan is not repaid
+#[test]
+fn flash_mint_not_repaid() {
+	ExtBuilder { balances: vec![(4, 10)], ..Default::default() }.build_and_execute(|| {
+		let transfer =
+			|value| Box::new(RuntimeCall::ERC20Module(crate::Call::transfer { to: 5, value }));
+		assert_noop!(
+			ERC20Module::flash_mint(RuntimeOrigin::signed(4), 500, transfer(500)),
+			Error::<Test>::FlashMintNotRepaid
+		);
+		// The fee cannot be paid either.
+		assert_noop!(
+			ERC20Module::flash_mint(RuntimeOrigin::signed(4), 500, transfer(6)),
+			Error::<Test>::FlashMintNotRepaid
+		);
+		assert_noop!(
+			ERC20Module::flash_mint(RuntimeOrigin::signed(4), 500, transfer(511)),
+			Error::<Test>::ERC20InsufficientBalance
+		);
+
+		assert_ok!(ERC20Module::flash_mint(RuntimeOrigin::signed(4), 500, transfer(5)));
+		assert_eq!(ERC20Module::balance_of(&4), 0);
+		assert_eq!(ERC20Module::balance_of(&5), 5);
+	});
+}
+
+/// Should not charge a flash mint fee the receiver could not hold, nor hide unrelated errors
+#[test]
+fn flash_mint_fee_waived() {
+	ExtBuilder::default().build_and_execute(|| {
+		let remark = Box::new(RuntimeCall::System(frame_system::Call::remark { remark: vec![] }));
+		assert_ok!(SecondERC20Module::mint(RuntimeOrigin::signed(2), 20));
+		assert_ok!(SecondERC20Module::transfer(RuntimeOrigin::signed(2), 4, 20));
+
+		// A fee of 1 would leave the receiver below the minimum balance of 5.
+		assert_ok!(SecondERC20Module::flash_mint(RuntimeOrigin::signed(4), 100, remark.clone()));
+		System::assert_last_event(
+			Event::<Test, Instance2>::FlashMinted { borrower: 4, amount: 100, fee: 0 }.into(),
+		);
+		assert_eq!(SecondERC20Module::balance_of(&4), 20);
+		assert_eq!(SecondERC20Module::balance_of(&FEE_COLLECTOR), 0);
+
+		assert_ok!(SecondERC20Module::flash_mint(RuntimeOrigin::signed(4), 500, remark.clone()));
+		assert_eq!(SecondERC20Module::balance_of(&4), 15);
+		assert_eq!(SecondERC20Module::balance_of(&FEE_COLLECTOR), 5);
+		// Once the receiver exists, small fees are charged.
+		assert_ok!(SecondERC20Module::flash_mint(RuntimeOrigin::signed(4), 100, remark.clone()));
+		assert_eq!(SecondERC20Module::balance_of(&4), 14);
+		assert_eq!(SecondERC20Module::balance_of(&FEE_COLLECTOR), 6);
+
+		assert_ok!(SecondERC20Module::pause(RuntimeOrigin::root(), Some(Operation::Burn)));
+		assert_noop!(
+			SecondERC20Module::flash_mint(RuntimeOrigin::signed(4), 100, remark),
+			Error::<Test, Instance2>::TokenPaused
+		);
+	});
+}
+
+/// Should keep frozen accounts from sending, receiving and spending allowances
+#[test]
+fn freeze_account_ok() {
+	ExtBuilder::default().build_and_execute(|| {
+		assert_ok!(ERC20Module::mint(RuntimeOrigin::signed(1), 100));
+		assert_ok!(ERC20Module::reserve(&1, 20));
+		assert_ok!(ERC20Module::approve(RuntimeOrigin::signed(1), 2, 50));
+		assert_ok!(ERC20Module::approve(RuntimeOrigin::signed(1), 3, 50));
+		assert_ok!(ERC20Module::grant_role(RuntimeOrigin::signed(10), Role::Burner, 3));
+		assert_noop!(
+			ERC20Module::freeze_account(RuntimeOrigin::signed(10), 2),
+			DispatchError::BadOrigin
+		);
+
+		assert_ok!(ERC20Module::freeze_account(RuntimeOrigin::root(), 2));
+		System::assert_last_event(Event::<Test>::AccountFrozen { account: 2 }.into());
+		assert!(ERC20Module::is_frozen(&2));
+		assert_noop!(
+			ERC20Module::transfer(RuntimeOrigin::signed(1), 2, 10),
+			Error::<Test>::AccountFrozen
+		);
+		assert_noop!(
+			ERC20Module::transfer_from(RuntimeOrigin::signed(2), 1, 3, 10),
+			Error::<Test>::AccountFrozen
+		);
+		assert_noop!(ERC20Module::mint_into(&2, 10), Error::<Test>::AccountFrozen);
+		assert_eq!(
+			ERC20Module::can_deposit(&2, 10, Provenance::Extant),
+			DepositConsequence::Blocked
+		);
+
+		assert_ok!(ERC20Module::freeze_account(RuntimeOrigin::root(), 1));
+		assert_noop!(
+			ERC20Module::transfer(RuntimeOrigin::signed(1), 3, 10),
+			Error::<Test>::AccountFrozen
+		);
+		assert_noop!(ERC20Module::mint(RuntimeOrigin::signed(1), 10), Error::<Test>::AccountFrozen);
+		assert_noop!(ERC20Module::burn(RuntimeOrigin::signed(1), 10), Error::<Test>::AccountFrozen);
+		assert_noop!(
+			ERC20Module::burn_from(RuntimeOrigin::signed(3), 1, 10),
+			Error::<Test>::AccountFrozen
+		);
+		assert_noop!(ERC20Module::reserve(&1, 10), Error::<Test>::AccountFrozen);
+		assert_noop!(
+			ERC20Module::repatriate_reserved(&1, &3, 10, BalanceStatus::Free),
+			Error::<Test>::AccountFrozen
+		);
+		assert_noop!(ERC20Module::write_balance(&1, 0), Error::<Test>::AccountFrozen);
+		assert_eq!(ERC20Module::can_withdraw(&1, 10), WithdrawConsequence::Frozen);
+
+		assert_ok!(ERC20Module::thaw_account(RuntimeOrigin::root(), 1));
+		assert_noop!(
+			ERC20Module::repatriate_reserved(&1, &2, 10, BalanceStatus::Free),
+			Error::<Test>::AccountFrozen
+		);
+		assert_ok!(ERC20Module::thaw_account(RuntimeOrigin::root(), 2));
+		System::assert_last_event(Event::<Test>::AccountThawed { account: 2 }.into());
+		assert_ok!(ERC20Module::transfer_from(RuntimeOrigin::signed(2), 1, 2, 10));
+		assert_eq!(ERC20Module::balance_of(&2), 10);
+	});
+}
+
+/// Should only let allowlisted accounts receive and transfer tokens while the allowlist is enabled
+#[test]
+fn allowlist_ok() {
+	ExtBuilder::default().build_and_execute(|| {
+		assert_ok!(ERC20Module::mint(RuntimeOrigin::signed(1), 100));
+		assert_noop!(
+			ERC20Module::set_allowlist_enabled(RuntimeOrigin::signed(10), true),
+			DispatchError::BadOrigin
+		);
+		assert_ok!(ERC20Module::set_allowlist_enabled(RuntimeOrigin::root(), true));
+		System::assert_last_event(Event::<Test>::AllowlistEnabledSet { enabled: true }.into());
+
+		assert_noop!(
+			ERC20Module::transfer(RuntimeOrigin::signed(1), 2, 10),
+			Error::<Test>::NotAllowlisted
+		);
+		assert_noop!(
+			ERC20Module::mint(RuntimeOrigin::signed(1), 10),
+			Error::<Test>::NotAllowlisted
+		);
+
+		assert_ok!(ERC20Module::add_to_allowlist(RuntimeOrigin::root(), 1));
+		System::assert_last_event(Event::<Test>::AddedToAllowlist { account: 1 }.into());
+		assert_ok!(ERC20Module::mint(RuntimeOrigin::signed(1), 10));
+		assert_noop!(
+			ERC20Module::transfer(RuntimeOrigin::signed(1), 2, 10),
+			Error::<Test>::NotAllowlisted
+		);
+		assert_ok!(ERC20Module::add_to_allowlist(RuntimeOrigin::root(), 2));
+		assert_ok!(ERC20Module::transfer(RuntimeOrigin::signed(1), 2, 10));
+
+		// Tokens cannot reach account 3 through the reserves or the fungible traits either.
+		assert_ok!(ERC20Module::reserve(&1, 10));
+		assert_noop!(
+			ERC20Module::repatriate_reserved(&1, &3, 10, BalanceStatus::Free),
+			Error::<Test>::NotAllowlisted
+		);
+		assert_eq!(ERC20Module::repatriate_reserved(&1, &2, 10, BalanceStatus::Free), Ok(0));
+		assert_noop!(
+			ERC20Module::increase_balance(&3, 10, Precision::Exact),
+			Error::<Test>::NotAllowlisted
+		);
+
+		assert_ok!(ERC20Module::remove_from_allowlist(RuntimeOrigin::root(), 1));
+		System::assert_last_event(Event::<Test>::RemovedFromAllowlist { account: 1 }.into());
+		assert_noop!(
+			ERC20Module::transfer(RuntimeOrigin::signed(2), 1, 10),
+			Error::<Test>::NotAllowlisted
+		);
+
+		assert_ok!(ERC20Module::set_allowlist_enabled(RuntimeOrigin::root(), false));
+		assert_ok!(ERC20Module::transfer(RuntimeOrigin::signed(2), 3, 10));
+	});
+}
+
+/// Should credit the fee accounts while the allowlist is enabled
+#[test]
+fn allowlist_fee_accounts() {
+	ExtBuilder::default().build_and_execute(|| {
+		let remark = Box::new(RuntimeCall::System(frame_system::Call::remark { remark: vec![] }));
+		assert_ok!(ERC20Module::mint(RuntimeOrigin::signed(1), 1000));
+		assert_ok!(ERC20Module::set_transfer_fee(RuntimeOrigin::root(), 100));
+		assert_ok!(ERC20Module::set_allowlist_enabled(RuntimeOrigin::root(), true));
+		assert_ok!(ERC20Module::add_to_allowlist(RuntimeOrigin::root(), 1));
+		assert_ok!(ERC20Module::add_to_allowlist(RuntimeOrigin::root(), 2));
+
+		assert_ok!(ERC20Module::transfer(RuntimeOrigin::signed(1), 2, 500));
+		assert_eq!(ERC20Module::balance_of(&2), 495);
+		assert_eq!(ERC20Module::balance_of(&TRANSFER_FEE_ACCOUNT), 5);
+		assert_ok!(ERC20Module::flash_mint(RuntimeOrigin::signed(2), 500, remark));
+		assert_eq!(ERC20Module::balance_of(&FEE_COLLECTOR), 5);
+
+		assert!(!ERC20Module::is_allowlisted(&TRANSFER_FEE_ACCOUNT));
+		assert_noop!(
+			ERC20Module::transfer(RuntimeOrigin::signed(TRANSFER_FEE_ACCOUNT), 3, 5),
+			Error::<Test>::NotAllowlisted
+		);
+	});
+}
+
+/// Should add and remove accounts of the allowlist in batches
+#[test]
+fn batch_allowlist_ok() {
+	ExtBuilder::default().build_and_execute(|| {
+		assert_noop!(
+			ERC20Module::batch_add_to_allowlist(RuntimeOrigin::root(), vec![1, 2, 3, 4]),
+			Error::<Test>::TooManyAccounts
+		);
+		assert_ok!(ERC20Module::batch_add_to_allowlist(RuntimeOrigin::root(), vec![1, 2, 3]));
+		System::assert_has_event(Event::<Test>::AddedToAllowlist { account: 2 }.into());
+		assert!([1, 2, 3].iter().all(ERC20Module::is_allowlisted));
+
+		assert_ok!(ERC20Module::batch_remove_from_allowlist(RuntimeOrigin::root(), vec![1, 3]));
+		System::assert_has_event(Event::<Test>::RemovedFromAllowlist { account: 1 }.into());
+		System::assert_last_event(Event::<Test>::RemovedFromAllowlist { account: 3 }.into());
+		assert!(!ERC20Module::is_allowlisted(&1));
+		assert!(ERC20Module::is_allowlisted(&2));
+	});
+}
+
+/// Should deduct the transfer fee from the amount and credit it to the fee account
+#[test]
+fn transfer_fee_ok() {
+	ExtBuilder::default().build_and_execute(|| {
+		assert_ok!(ERC20Module::mint(RuntimeOrigin::signed(1), 2000));
+		assert_noop!(
+			ERC20Module::set_transfer_fee(RuntimeOrigin::signed(10), 30),
+			DispatchError::BadOrigin
+		);
+		assert_noop!(
+			ERC20Module::set_transfer_fee(RuntimeOrigin::root(), 101),
+			Error::<Test>::TransferFeeTooHigh
+		);
+		assert_ok!(ERC20Module::set_transfer_fee(RuntimeOrigin::root(), 30));
+		System::assert_last_event(Event::<Test>::TransferFeeSet { bps: 30 }.into());
+
+		assert_ok!(ERC20Module::transfer(RuntimeOrigin::signed(1), 2, 1000));
+		System::assert_has_event(
+			Event::<Test>::Transfer { from: 1, to: TRANSFER_FEE_ACCOUNT, value: 3 }.into(),
+		);
+		System::assert_has_event(Event::<Test>::Transfer { from: 1, to: 2, value: 997 }.into());
+		System::assert_last_event(Event::<Test>::FeeCharged { from: 1, to: 2, fee: 3 }.into());
+		assert_eq!(ERC20Module::balance_of(&1), 1000);
+		assert_eq!(ERC20Module::balance_of(&2), 997);
+		assert_eq!(ERC20Module::balance_of(&TRANSFER_FEE_ACCOUNT), 3);
+
+		assert_ok!(ERC20Module::approve(RuntimeOrigin::signed(1), 3, 1000));
+		assert_ok!(ERC20Module::transfer_from(RuntimeOrigin::signed(3), 1, 4, 1000));
+		assert_eq!(ERC20Module::balance_of(&1), 0);
+		assert_eq!(ERC20Module::balance_of(&4), 997);
+		assert_eq!(ERC20Module::balance_of(&TRANSFER_FEE_ACCOUNT), 6);
+		assert_eq!(ERC20Module::total_supply(), 2000);
+	});
+}
+
+/// Should not charge a fee the fee account could not receive, nor on transfers to oneself
+#[test]
+fn transfer_fee_waived() {
+	ExtBuilder::default().build_and_execute(|| {
+		assert_ok!(SecondERC20Module::mint(RuntimeOrigin::signed(2), 2000));
+		assert_ok!(SecondERC20Module::set_transfer_fee(RuntimeOrigin::root(), 100));
+
+		// A fee of 1 would leave the fee account below the minimum balance of 5.
+		assert_ok!(SecondERC20Module::transfer(RuntimeOrigin::signed(2), 3, 100));
+		assert_eq!(SecondERC20Module::balance_of(&3), 100);
+		assert_eq!(SecondERC20Module::balance_of(&TRANSFER_FEE_ACCOUNT), 0);
+
+		assert_ok!(SecondERC20Module::transfer(RuntimeOrigin::signed(2), 3, 500));
+		assert_eq!(SecondERC20Module::balance_of(&3), 595);
+		assert_eq!(SecondERC20Module::balance_of(&TRANSFER_FEE_ACCOUNT), 5);
+		// Once the fee account exists, small fees are charged.
+		assert_ok!(SecondERC20Module::transfer(RuntimeOrigin::signed(2), 3, 100));
+		assert_eq!(SecondERC20Module::balance_of(&3), 694);
+		assert_eq!(SecondERC20Module::balance_of(&TRANSFER_FEE_ACCOUNT), 6);
+
+		assert_ok!(SecondERC20Module::transfer(RuntimeOrigin::signed(3), 3, 500));
+		System::assert_last_event(
+			Event::<Test, Instance2>::Transfer { from: 3, to: 3, value: 500 }.into(),
+		);
+		assert_eq!(SecondERC20Module::balance_of(&3), 694);
+		assert_eq!(SecondERC20Module::balance_of(&TRANSFER_FEE_ACCOUNT), 6);
+	});
+}
+
+/// Should not charge the transfer fee on transfers of exempt accounts
+#[test]
+fn transfer_fee_exempt() {
+	ExtBuilder::default().build_and_execute(|| {
+		assert_ok!(ERC20Module::mint(RuntimeOrigin::signed(1), 2000));
+		assert_ok!(ERC20Module::set_transfer_fee(RuntimeOrigin::root(), 100));
+		assert_ok!(ERC20Module::set_transfer_fee_exempt(RuntimeOrigin::root(), 2, true));
+		System::assert_last_event(
+			Event::<Test>::TransferFeeExemptionSet { account: 2, exempt: true }.into(),
+		);
+
+		assert_ok!(ERC20Module::transfer(RuntimeOrigin::signed(1), 2, 1000));
+		assert_ok!(ERC20Module::transfer(RuntimeOrigin::signed(2), 3, 500));
+		assert_eq!(ERC20Module::balance_of(&2), 500);
+		assert_eq!(ERC20Module::balance_of(&3), 500);
+		assert_eq!(ERC20Module::balance_of(&TRANSFER_FEE_ACCOUNT), 0);
+
+		assert_ok!(ERC20Module::set_transfer_fee_exempt(RuntimeOrigin::root(), 2, false));
+		assert_ok!(ERC20Module::transfer(RuntimeOrigin::signed(2), 3, 500));
+		assert_eq!(ERC20Module::balance_of(&3), 995);
+		assert_eq!(ERC20Module::balance_of(&TRANSFER_FEE_ACCOUNT), 5);
+	});
+}
@@ -0,0 +1,139 @@
+//! Types used by the ERC20 pallet.
+
+use codec::{Decode, Encode, MaxEncodedLen};
+use frame_support::{
+	sp_runtime::traits::{AtLeast32BitUnsigned, Convert, Saturating},
+	PalletError, RuntimeDebug,
+};
+use scale_info::TypeInfo;
+
+/// Roles of the token access control.
+///
+/// Every role is administered by another role, `Admin` by default.
+#[derive(
+	Clone, Copy, Encode, Decode, Eq, PartialEq, RuntimeDebug, MaxEncodedLen, TypeInfo, PalletError,
+)]
+pub enum Role {
+	/// Grants and revokes the roles it administers.
+	Admin,
+	/// Allowed to call `mint`.
+	Minter,
+	/// Allowed to call `burn`.
+	Burner,
+	/// Allowed to pause and unpause the token.
+	Pauser,
+}
+
+/// Token operations that can be paused separately.
+#[derive(Clone, Copy, Encode, Decode, Eq, PartialEq, RuntimeDebug, MaxEncodedLen, TypeInfo)]
+pub enum Operation {
+	/// Moving tokens between accounts.
+	Transfer,
+	/// Creating tokens.
+	Mint,
+	/// Destroying tokens.
+	Burn,
+}
+
+/// Amount a minter may still mint, set by the admins of `Role::Minter`.
+#[derive(Clone, Encode, Decode, Eq, PartialEq, RuntimeDebug, MaxEncodedLen, TypeInfo)]
+pub struct MintQuota<Balance, BlockNumber> {
+	/// Amount left until the quota is refilled.
+	pub remaining: Balance,
+	/// Optional limit on the amount minted per period.
+	pub rate_limit: Option<MintRateLimit<Balance, BlockNumber>>,
+}
+
+/// Limit on the amount minted within a period of blocks.
+#[derive(Clone, Encode, Decode, Eq, PartialEq, RuntimeDebug, MaxEncodedLen, TypeInfo)]
+pub struct MintRateLimit<Balance, BlockNumber> {
+	/// Maximum amount minted per period.
+	pub max_per_period: Balance,
+	/// Length of a period in blocks.
+	pub period: BlockNumber,
+	/// First block of the current period.
+	pub period_start: BlockNumber,
+	/// Amount minted in the current period.
+	pub minted: Balance,
+}
+
+/// Payload an owner signs off-chain to `permit` an allowance.
+///
+/// The genesis hash and pallet index keep a signature from being replayed on another chain or
+/// token instance, the nonce from being replayed on the same one.
+#[derive(Clone, Encode, Decode, Eq, PartialEq, RuntimeDebug, TypeInfo)]
+pub struct PermitPayload<Hash, AccountId, Balance, BlockNumber> {
+	/// Hash of the genesis block of the chain.
+	pub genesis_hash: Hash,
+	/// Index of the token pallet instance in the runtime.
+	pub pallet_index: u8,
+	/// Account granting the allowance.
+	pub owner: AccountId,
+	/// Account allowed to spend.
+	pub spender: AccountId,
+	/// New allowance.
+	pub value: Balance,
+	/// Current permit nonce of the owner.
+	pub nonce: u64,
+	/// Last block the permit can be used in.
+	pub deadline: BlockNumber,
+}
+
+/// Linear unlocking of tokens transferred with `vested_transfer` or given at genesis.
+///
+/// `per_block` tokens unlock every block from `start`, but none can be spent before the `cliff`
+/// blocks after `start` have passed.
+#[derive(Clone, Copy, Encode, Decode, Eq, PartialEq, RuntimeDebug, MaxEncodedLen, TypeInfo)]
+pub struct VestingSchedule<Balance, BlockNumber> {
+	/// Amount locked at `start`.
+	pub locked: Balance,
+	/// Amount unlocked every block.
+	pub per_block: Balance,
+	/// First block of the unlocking.
+	pub start: BlockNumber,
+	/// Number of blocks after `start` during which nothing can be spent.
+	pub cliff: BlockNumber,
+}
+
+impl<Balance, BlockNumber> VestingSchedule<Balance, BlockNumber>
+where
+	Balance: AtLeast32BitUnsigned + Copy,
+	BlockNumber: AtLeast32BitUnsigned + Copy,
+{
+	/// Amount still locked at block `now`.
+	pub fn locked_at<BlockNumberToBalance: Convert<BlockNumber, Balance>>(
+		&self,
+		now: BlockNumber,
+	) -> Balance {
+		if now < self.start.saturating_add(self.cliff) {
+			return self.locked
+		}
+		let vested = BlockNumberToBalance::convert(now.saturating_sub(self.start))
+			.saturating_mul(self.per_block);
+		self.locked.saturating_sub(vested)
+	}
+}
+
+/// Identifier of a snapshot taken by `snapshot`, starting at 1.
+pub type SnapshotId = u32;
+
+/// Value at snapshot `id`, recorded when it first changed after the snapshot.
+///
+/// Checkpoints are kept by index in snapshot order, a checkpoint holding the value at every
+/// snapshot since the one of the previous checkpoint.
+#[derive(Clone, Copy, Encode, Decode, Eq, PartialEq, RuntimeDebug, MaxEncodedLen, TypeInfo)]
+pub struct Checkpoint<Balance> {
+	/// Snapshot the value was taken at.
+	pub id: SnapshotId,
+	/// Value when the snapshot was taken.
+	pub value: Balance,
+}
+
+/// Voting power of a delegate, or total supply, from block `from_block` on.
+#[derive(Clone, Copy, Encode, Decode, Eq, PartialEq, RuntimeDebug, MaxEncodedLen, TypeInfo)]
+pub struct VoteCheckpoint<Balance, BlockNumber> {
+	/// First block of the value.
+	pub from_block: BlockNumber,
+	/// Votes or total supply since `from_block`.
+	pub votes: Balance,
+}
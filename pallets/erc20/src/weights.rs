@@ -1,11 +1,11 @@
-
-//! Autogenerated weights for `erc20`
+//! Weights for `erc20`
 //!
-//! THIS FILE WAS AUTO-GENERATED USING THE SUBSTRATE BENCHMARK CLI VERSION 4.0.0-dev
-//! DATE: 2023-09-18, STEPS: `50`, REPEAT: `20`, LOW RANGE: `[]`, HIGH RANGE: `[]`
-//! WORST CASE MAP SIZE: `1000000`
-//! HOSTNAME: `cryptonuser14079-LT`, CPU: `11th Gen Intel(R) Core(TM) i5-1155G7 @ 2.50GHz`
-//! WASM-EXECUTION: `Compiled`, CHAIN: `Some("dev")`, DB CACHE: `1024`
+//! This file is not benchmark output. Only `transfer`, `transfer_from`, `approve`, `mint` and
+//! `burn` were ever measured, on 2023-09-18, before most of their current storage accesses existed.
+//! Every weight below is a hand estimate: execution time is scaled from those measurements,
+//! database reads and writes are counted from the code for the worst case. Replace this file with
+//! the output of the command below, run for both the `UsdToken` and `GovToken` instances, before
+//! production use.
 
 // Executed Command:
 // ./target/release/node-template
@@ -37,137 +37,409 @@ pub trait WeightInfo {
 	fn approve() -> Weight;
 	fn mint() -> Weight;
 	fn burn() -> Weight;
+	fn set_metadata() -> Weight;
+	fn grant_role() -> Weight;
+	fn revoke_role() -> Weight;
+	fn set_role_admin() -> Weight;
+	fn pause() -> Weight;
+	fn unpause() -> Weight;
+	fn set_mint_quota() -> Weight;
+	fn clear_mint_quota() -> Weight;
+	fn increase_allowance() -> Weight;
+	fn decrease_allowance() -> Weight;
+	fn burn_from() -> Weight;
+	fn permit() -> Weight;
+	fn batch_transfer(n: u32, ) -> Weight;
+	fn set_fee_rate() -> Weight;
+	fn transfer_keep_alive() -> Weight;
+	fn remove_allowance() -> Weight;
+	fn vested_transfer() -> Weight;
+	fn vest() -> Weight;
+	fn snapshot() -> Weight;
+	fn delegate() -> Weight;
+	fn flash_mint() -> Weight;
+	fn freeze_account() -> Weight;
+	fn thaw_account() -> Weight;
+	fn add_to_allowlist() -> Weight;
+	fn remove_from_allowlist() -> Weight;
+	fn batch_add_to_allowlist(n: u32, ) -> Weight;
+	fn batch_remove_from_allowlist(n: u32, ) -> Weight;
+	fn set_allowlist_enabled() -> Weight;
+	fn set_transfer_fee() -> Weight;
+	fn set_transfer_fee_exempt() -> Weight;
 }
 
 /// Weights for `erc20` using the Substrate node and recommended hardware.
 pub struct SubstrateWeight<T>(PhantomData<T>);
 impl<T: frame_system::Config> WeightInfo for SubstrateWeight<T> {
-	/// Storage: `ERCModule::Balances` (r:2 w:2)
-	/// Proof: `ERCModule::Balances` (`max_values`: None, `max_size`: Some(56), added: 2531, mode: `MaxEncodedLen`)
+	/// Worst case: the fee is charged, so `_transfer` runs twice, and the sender, the recipient and
+	/// `Config::TransferFeeAccount` each checkpoint a snapshot and move the votes of a delegate.
+	/// The sender is reaped and its dust burnt.
 	fn transfer() -> Weight {
-		// Proof Size summary in bytes:
-		//  Measured:  `119`
-		//  Estimated: `6052`
-		// Minimum execution time: 18_319_000 picoseconds.
-		Weight::from_parts(19_342_000, 6052)
+		Weight::from_parts(38_684_000, 83157)
+			.saturating_add(T::DbWeight::get().reads(40_u64))
+			.saturating_add(T::DbWeight::get().writes(22_u64))
+	}
+	/// Worst case: the fee is charged, so `_transfer` runs twice, and the sender, the recipient and
+	/// `Config::TransferFeeAccount` each checkpoint a snapshot and move the votes of a delegate.
+	/// The allowance is spent and its deposit released, the owner is reaped and its dust burnt.
+	fn transfer_from() -> Weight {
+		Weight::from_parts(49_456_000, 93481)
+			.saturating_add(T::DbWeight::get().reads(44_u64))
+			.saturating_add(T::DbWeight::get().writes(25_u64))
+	}
+	fn approve() -> Weight {
+		Weight::from_parts(10_985_000, 5182)
 			.saturating_add(T::DbWeight::get().reads(2_u64))
+			.saturating_add(T::DbWeight::get().writes(3_u64))
+	}
+	fn mint() -> Weight {
+		Weight::from_parts(14_390_000, 13155)
+			.saturating_add(T::DbWeight::get().reads(20_u64))
+			.saturating_add(T::DbWeight::get().writes(11_u64))
+	}
+	fn burn() -> Weight {
+		Weight::from_parts(13_904_000, 13155)
+			.saturating_add(T::DbWeight::get().reads(17_u64))
+			.saturating_add(T::DbWeight::get().writes(10_u64))
+	}
+	fn set_metadata() -> Weight {
+		Weight::from_parts(12_631_000, 0)
+			.saturating_add(T::DbWeight::get().writes(3_u64))
+	}
+	fn grant_role() -> Weight {
+		Weight::from_parts(20_113_000, 6070)
+			.saturating_add(T::DbWeight::get().reads(4_u64))
 			.saturating_add(T::DbWeight::get().writes(2_u64))
 	}
-	/// Storage: `ERCModule::Allowances` (r:1 w:1)
-	/// Proof: `ERCModule::Allowances` (`max_values`: None, `max_size`: Some(104), added: 2579, mode: `MaxEncodedLen`)
-	/// Storage: `ERCModule::Balances` (r:2 w:2)
-	/// Proof: `ERCModule::Balances` (`max_values`: None, `max_size`: Some(56), added: 2531, mode: `MaxEncodedLen`)
-	fn transfer_from() -> Weight {
-		// Proof Size summary in bytes:
-		//  Measured:  `245`
-		//  Estimated: `6052`
-		// Minimum execution time: 28_886_000 picoseconds.
-		Weight::from_parts(30_114_000, 6052)
+	fn revoke_role() -> Weight {
+		Weight::from_parts(21_577_000, 6070)
+			.saturating_add(T::DbWeight::get().reads(4_u64))
+			.saturating_add(T::DbWeight::get().writes(2_u64))
+	}
+	fn set_role_admin() -> Weight {
+		Weight::from_parts(11_690_000, 3483)
+			.saturating_add(T::DbWeight::get().reads(1_u64))
+			.saturating_add(T::DbWeight::get().writes(1_u64))
+	}
+	fn pause() -> Weight {
+		Weight::from_parts(12_973_000, 3530)
+			.saturating_add(T::DbWeight::get().reads(1_u64))
+			.saturating_add(T::DbWeight::get().writes(1_u64))
+	}
+	fn unpause() -> Weight {
+		Weight::from_parts(13_148_000, 3530)
+			.saturating_add(T::DbWeight::get().reads(1_u64))
+			.saturating_add(T::DbWeight::get().writes(1_u64))
+	}
+	fn set_mint_quota() -> Weight {
+		Weight::from_parts(12_682_000, 3530)
+			.saturating_add(T::DbWeight::get().reads(2_u64))
+			.saturating_add(T::DbWeight::get().writes(1_u64))
+	}
+	fn clear_mint_quota() -> Weight {
+		Weight::from_parts(13_905_000, 3549)
+			.saturating_add(T::DbWeight::get().reads(3_u64))
+			.saturating_add(T::DbWeight::get().writes(1_u64))
+	}
+	fn increase_allowance() -> Weight {
+		Weight::from_parts(13_690_000, 5182)
 			.saturating_add(T::DbWeight::get().reads(3_u64))
 			.saturating_add(T::DbWeight::get().writes(3_u64))
 	}
-	/// Storage: `ERCModule::Allowances` (r:0 w:1)
-	/// Proof: `ERCModule::Allowances` (`max_values`: None, `max_size`: Some(104), added: 2579, mode: `MaxEncodedLen`)
-	fn approve() -> Weight {
-		// Proof Size summary in bytes:
-		//  Measured:  `0`
-		//  Estimated: `0`
-		// Minimum execution time: 10_752_000 picoseconds.
-		Weight::from_parts(10_985_000, 0)
+	fn decrease_allowance() -> Weight {
+		Weight::from_parts(13_601_000, 3569)
+			.saturating_add(T::DbWeight::get().reads(3_u64))
+			.saturating_add(T::DbWeight::get().writes(3_u64))
+	}
+	fn burn_from() -> Weight {
+		Weight::from_parts(30_508_000, 13194)
+			.saturating_add(T::DbWeight::get().reads(21_u64))
+			.saturating_add(T::DbWeight::get().writes(13_u64))
+	}
+	fn permit() -> Weight {
+		Weight::from_parts(63_107_000, 3521)
+			.saturating_add(T::DbWeight::get().reads(5_u64))
+			.saturating_add(T::DbWeight::get().writes(4_u64))
+	}
+	/// Worst case: the fee is charged, so `_transfer` runs twice, and the sender, every recipient and
+	/// `Config::TransferFeeAccount` each checkpoint a snapshot and move the votes of a delegate.
+	/// The sender is reaped and its dust burnt by the last transfer.
+	fn batch_transfer(n: u32, ) -> Weight {
+		Weight::from_parts(10_000_000, 57795)
+			.saturating_add(Weight::from_parts(38_684_000, 0).saturating_mul(n.into()))
+			.saturating_add(T::DbWeight::get().reads(30_u64))
+			.saturating_add(T::DbWeight::get().reads((10_u64).saturating_mul(n.into())))
+			.saturating_add(T::DbWeight::get().writes(17_u64))
+			.saturating_add(T::DbWeight::get().writes((5_u64).saturating_mul(n.into())))
+			.saturating_add(Weight::from_parts(0, 25362).saturating_mul(n.into()))
+	}
+	fn set_fee_rate() -> Weight {
+		Weight::from_parts(9_241_000, 0)
 			.saturating_add(T::DbWeight::get().writes(1_u64))
 	}
-	/// Storage: `ERCModule::Minters` (r:1 w:0)
-	/// Proof: `ERCModule::Minters` (`max_values`: None, `max_size`: Some(48), added: 2523, mode: `MaxEncodedLen`)
-	/// Storage: `ERCModule::Balances` (r:1 w:1)
-	/// Proof: `ERCModule::Balances` (`max_values`: None, `max_size`: Some(56), added: 2531, mode: `MaxEncodedLen`)
-	/// Storage: `ERCModule::TotalSupply` (r:0 w:1)
-	/// Proof: `ERCModule::TotalSupply` (`max_values`: Some(1), `max_size`: Some(8), added: 503, mode: `MaxEncodedLen`)
-	fn mint() -> Weight {
-		// Proof Size summary in bytes:
-		//  Measured:  `218`
-		//  Estimated: `3521`
-		// Minimum execution time: 12_860_000 picoseconds.
-		Weight::from_parts(13_182_000, 3521)
-			.saturating_add(T::DbWeight::get().reads(2_u64))
-			.saturating_add(T::DbWeight::get().writes(2_u64))
+	/// Worst case: the fee is charged, so `_transfer` runs twice, and the sender, the recipient and
+	/// `Config::TransferFeeAccount` each checkpoint a snapshot and move the votes of a delegate.
+	fn transfer_keep_alive() -> Weight {
+		Weight::from_parts(38_684_000, 80582)
+			.saturating_add(T::DbWeight::get().reads(35_u64))
+			.saturating_add(T::DbWeight::get().writes(15_u64))
 	}
-	/// Storage: `ERCModule::Balances` (r:1 w:1)
-	/// Proof: `ERCModule::Balances` (`max_values`: None, `max_size`: Some(56), added: 2531, mode: `MaxEncodedLen`)
-	/// Storage: `ERCModule::TotalSupply` (r:0 w:1)
-	/// Proof: `ERCModule::TotalSupply` (`max_values`: Some(1), `max_size`: Some(8), added: 503, mode: `MaxEncodedLen`)
-	fn burn() -> Weight {
-		// Proof Size summary in bytes:
-		//  Measured:  `119`
-		//  Estimated: `3521`
-		// Minimum execution time: 10_277_000 picoseconds.
-		Weight::from_parts(10_597_000, 3521)
+	fn remove_allowance() -> Weight {
+		Weight::from_parts(22_610_000, 3593)
+			.saturating_add(T::DbWeight::get().reads(3_u64))
+			.saturating_add(T::DbWeight::get().writes(3_u64))
+	}
+	fn vested_transfer() -> Weight {
+		Weight::from_parts(42_871_000, 21801)
+			.saturating_add(T::DbWeight::get().reads(27_u64))
+			.saturating_add(T::DbWeight::get().writes(12_u64))
+	}
+	fn vest() -> Weight {
+		Weight::from_parts(18_015_000, 4634)
 			.saturating_add(T::DbWeight::get().reads(1_u64))
 			.saturating_add(T::DbWeight::get().writes(2_u64))
 	}
+	fn snapshot() -> Weight {
+		Weight::from_parts(7_285_000, 1489)
+			.saturating_add(T::DbWeight::get().reads(1_u64))
+			.saturating_add(T::DbWeight::get().writes(1_u64))
+	}
+	fn delegate() -> Weight {
+		Weight::from_parts(28_637_000, 6108)
+			.saturating_add(T::DbWeight::get().reads(6_u64))
+			.saturating_add(T::DbWeight::get().writes(5_u64))
+	}
+	fn flash_mint() -> Weight {
+		Weight::from_parts(73_418_000, 11755)
+			.saturating_add(T::DbWeight::get().reads(31_u64))
+			.saturating_add(T::DbWeight::get().writes(17_u64))
+	}
+	fn freeze_account() -> Weight {
+		Weight::from_parts(9_102_000, 0)
+			.saturating_add(T::DbWeight::get().writes(1_u64))
+	}
+	fn thaw_account() -> Weight {
+		Weight::from_parts(8_973_000, 0)
+			.saturating_add(T::DbWeight::get().writes(1_u64))
+	}
+	fn add_to_allowlist() -> Weight {
+		Weight::from_parts(9_215_000, 0)
+			.saturating_add(T::DbWeight::get().writes(1_u64))
+	}
+	fn remove_from_allowlist() -> Weight {
+		Weight::from_parts(9_037_000, 0)
+			.saturating_add(T::DbWeight::get().writes(1_u64))
+	}
+	/// The range of component `n` is `[1, 500]`.
+	fn batch_add_to_allowlist(n: u32, ) -> Weight {
+		Weight::from_parts(5_406_231, 0)
+			.saturating_add(Weight::from_parts(2_961_774, 0).saturating_mul(n.into()))
+			.saturating_add(T::DbWeight::get().writes((1_u64).saturating_mul(n.into())))
+	}
+	/// The range of component `n` is `[1, 500]`.
+	fn batch_remove_from_allowlist(n: u32, ) -> Weight {
+		Weight::from_parts(5_218_904, 0)
+			.saturating_add(Weight::from_parts(2_904_319, 0).saturating_mul(n.into()))
+			.saturating_add(T::DbWeight::get().writes((1_u64).saturating_mul(n.into())))
+	}
+	fn set_allowlist_enabled() -> Weight {
+		Weight::from_parts(6_712_000, 0)
+			.saturating_add(T::DbWeight::get().writes(1_u64))
+	}
+	fn set_transfer_fee() -> Weight {
+		Weight::from_parts(6_874_000, 0)
+			.saturating_add(T::DbWeight::get().writes(1_u64))
+	}
+	fn set_transfer_fee_exempt() -> Weight {
+		Weight::from_parts(9_146_000, 0)
+			.saturating_add(T::DbWeight::get().writes(1_u64))
+	}
 }
 
 // For backwards compatibility and tests.
 impl WeightInfo for () {
-	/// Storage: `ERCModule::Balances` (r:2 w:2)
-	/// Proof: `ERCModule::Balances` (`max_values`: None, `max_size`: Some(56), added: 2531, mode: `MaxEncodedLen`)
+	/// Worst case: the fee is charged, so `_transfer` runs twice, and the sender, the recipient and
+	/// `Config::TransferFeeAccount` each checkpoint a snapshot and move the votes of a delegate.
+	/// The sender is reaped and its dust burnt.
 	fn transfer() -> Weight {
-		// Proof Size summary in bytes:
-		//  Measured:  `119`
-		//  Estimated: `6052`
-		// Minimum execution time: 18_319_000 picoseconds.
-		Weight::from_parts(19_342_000, 6052)
+		Weight::from_parts(38_684_000, 83157)
+			.saturating_add(RocksDbWeight::get().reads(40_u64))
+			.saturating_add(RocksDbWeight::get().writes(22_u64))
+	}
+	/// Worst case: the fee is charged, so `_transfer` runs twice, and the sender, the recipient and
+	/// `Config::TransferFeeAccount` each checkpoint a snapshot and move the votes of a delegate.
+	/// The allowance is spent and its deposit released, the owner is reaped and its dust burnt.
+	fn transfer_from() -> Weight {
+		Weight::from_parts(49_456_000, 93481)
+			.saturating_add(RocksDbWeight::get().reads(44_u64))
+			.saturating_add(RocksDbWeight::get().writes(25_u64))
+	}
+	fn approve() -> Weight {
+		Weight::from_parts(10_985_000, 5182)
 			.saturating_add(RocksDbWeight::get().reads(2_u64))
+			.saturating_add(RocksDbWeight::get().writes(3_u64))
+	}
+	fn mint() -> Weight {
+		Weight::from_parts(14_390_000, 13155)
+			.saturating_add(RocksDbWeight::get().reads(20_u64))
+			.saturating_add(RocksDbWeight::get().writes(11_u64))
+	}
+	fn burn() -> Weight {
+		Weight::from_parts(13_904_000, 13155)
+			.saturating_add(RocksDbWeight::get().reads(17_u64))
+			.saturating_add(RocksDbWeight::get().writes(10_u64))
+	}
+	fn set_metadata() -> Weight {
+		Weight::from_parts(12_631_000, 0)
+			.saturating_add(RocksDbWeight::get().writes(3_u64))
+	}
+	fn grant_role() -> Weight {
+		Weight::from_parts(20_113_000, 6070)
+			.saturating_add(RocksDbWeight::get().reads(4_u64))
 			.saturating_add(RocksDbWeight::get().writes(2_u64))
 	}
-	/// Storage: `ERCModule::Allowances` (r:1 w:1)
-	/// Proof: `ERCModule::Allowances` (`max_values`: None, `max_size`: Some(104), added: 2579, mode: `MaxEncodedLen`)
-	/// Storage: `ERCModule::Balances` (r:2 w:2)
-	/// Proof: `ERCModule::Balances` (`max_values`: None, `max_size`: Some(56), added: 2531, mode: `MaxEncodedLen`)
-	fn transfer_from() -> Weight {
-		// Proof Size summary in bytes:
-		//  Measured:  `245`
-		//  Estimated: `6052`
-		// Minimum execution time: 28_886_000 picoseconds.
-		Weight::from_parts(30_114_000, 6052)
+	fn revoke_role() -> Weight {
+		Weight::from_parts(21_577_000, 6070)
+			.saturating_add(RocksDbWeight::get().reads(4_u64))
+			.saturating_add(RocksDbWeight::get().writes(2_u64))
+	}
+	fn set_role_admin() -> Weight {
+		Weight::from_parts(11_690_000, 3483)
+			.saturating_add(RocksDbWeight::get().reads(1_u64))
+			.saturating_add(RocksDbWeight::get().writes(1_u64))
+	}
+	fn pause() -> Weight {
+		Weight::from_parts(12_973_000, 3530)
+			.saturating_add(RocksDbWeight::get().reads(1_u64))
+			.saturating_add(RocksDbWeight::get().writes(1_u64))
+	}
+	fn unpause() -> Weight {
+		Weight::from_parts(13_148_000, 3530)
+			.saturating_add(RocksDbWeight::get().reads(1_u64))
+			.saturating_add(RocksDbWeight::get().writes(1_u64))
+	}
+	fn set_mint_quota() -> Weight {
+		Weight::from_parts(12_682_000, 3530)
+			.saturating_add(RocksDbWeight::get().reads(2_u64))
+			.saturating_add(RocksDbWeight::get().writes(1_u64))
+	}
+	fn clear_mint_quota() -> Weight {
+		Weight::from_parts(13_905_000, 3549)
+			.saturating_add(RocksDbWeight::get().reads(3_u64))
+			.saturating_add(RocksDbWeight::get().writes(1_u64))
+	}
+	fn increase_allowance() -> Weight {
+		Weight::from_parts(13_690_000, 5182)
 			.saturating_add(RocksDbWeight::get().reads(3_u64))
 			.saturating_add(RocksDbWeight::get().writes(3_u64))
 	}
-	/// Storage: `ERCModule::Allowances` (r:0 w:1)
-	/// Proof: `ERCModule::Allowances` (`max_values`: None, `max_size`: Some(104), added: 2579, mode: `MaxEncodedLen`)
-	fn approve() -> Weight {
-		// Proof Size summary in bytes:
-		//  Measured:  `0`
-		//  Estimated: `0`
-		// Minimum execution time: 10_752_000 picoseconds.
-		Weight::from_parts(10_985_000, 0)
+	fn decrease_allowance() -> Weight {
+		Weight::from_parts(13_601_000, 3569)
+			.saturating_add(RocksDbWeight::get().reads(3_u64))
+			.saturating_add(RocksDbWeight::get().writes(3_u64))
+	}
+	fn burn_from() -> Weight {
+		Weight::from_parts(30_508_000, 13194)
+			.saturating_add(RocksDbWeight::get().reads(21_u64))
+			.saturating_add(RocksDbWeight::get().writes(13_u64))
+	}
+	fn permit() -> Weight {
+		Weight::from_parts(63_107_000, 3521)
+			.saturating_add(RocksDbWeight::get().reads(5_u64))
+			.saturating_add(RocksDbWeight::get().writes(4_u64))
+	}
+	/// Worst case: the fee is charged, so `_transfer` runs twice, and the sender, every recipient and
+	/// `Config::TransferFeeAccount` each checkpoint a snapshot and move the votes of a delegate.
+	/// The sender is reaped and its dust burnt by the last transfer.
+	fn batch_transfer(n: u32, ) -> Weight {
+		Weight::from_parts(10_000_000, 57795)
+			.saturating_add(Weight::from_parts(38_684_000, 0).saturating_mul(n.into()))
+			.saturating_add(RocksDbWeight::get().reads(30_u64))
+			.saturating_add(RocksDbWeight::get().reads((10_u64).saturating_mul(n.into())))
+			.saturating_add(RocksDbWeight::get().writes(17_u64))
+			.saturating_add(RocksDbWeight::get().writes((5_u64).saturating_mul(n.into())))
+			.saturating_add(Weight::from_parts(0, 25362).saturating_mul(n.into()))
+	}
+	fn set_fee_rate() -> Weight {
+		Weight::from_parts(9_241_000, 0)
 			.saturating_add(RocksDbWeight::get().writes(1_u64))
 	}
-	/// Storage: `ERCModule::Minters` (r:1 w:0)
-	/// Proof: `ERCModule::Minters` (`max_values`: None, `max_size`: Some(48), added: 2523, mode: `MaxEncodedLen`)
-	/// Storage: `ERCModule::Balances` (r:1 w:1)
-	/// Proof: `ERCModule::Balances` (`max_values`: None, `max_size`: Some(56), added: 2531, mode: `MaxEncodedLen`)
-	/// Storage: `ERCModule::TotalSupply` (r:0 w:1)
-	/// Proof: `ERCModule::TotalSupply` (`max_values`: Some(1), `max_size`: Some(8), added: 503, mode: `MaxEncodedLen`)
-	fn mint() -> Weight {
-		// Proof Size summary in bytes:
-		//  Measured:  `218`
-		//  Estimated: `3521`
-		// Minimum execution time: 12_860_000 picoseconds.
-		Weight::from_parts(13_182_000, 3521)
-			.saturating_add(RocksDbWeight::get().reads(2_u64))
-			.saturating_add(RocksDbWeight::get().writes(2_u64))
+	/// Worst case: the fee is charged, so `_transfer` runs twice, and the sender, the recipient and
+	/// `Config::TransferFeeAccount` each checkpoint a snapshot and move the votes of a delegate.
+	fn transfer_keep_alive() -> Weight {
+		Weight::from_parts(38_684_000, 80582)
+			.saturating_add(RocksDbWeight::get().reads(35_u64))
+			.saturating_add(RocksDbWeight::get().writes(15_u64))
 	}
-	/// Storage: `ERCModule::Balances` (r:1 w:1)
-	/// Proof: `ERCModule::Balances` (`max_values`: None, `max_size`: Some(56), added: 2531, mode: `MaxEncodedLen`)
-	/// Storage: `ERCModule::TotalSupply` (r:0 w:1)
-	/// Proof: `ERCModule::TotalSupply` (`max_values`: Some(1), `max_size`: Some(8), added: 503, mode: `MaxEncodedLen`)
-	fn burn() -> Weight {
-		// Proof Size summary in bytes:
-		//  Measured:  `119`
-		//  Estimated: `3521`
-		// Minimum execution time: 10_277_000 picoseconds.
-		Weight::from_parts(10_597_000, 3521)
+	fn remove_allowance() -> Weight {
+		Weight::from_parts(22_610_000, 3593)
+			.saturating_add(RocksDbWeight::get().reads(3_u64))
+			.saturating_add(RocksDbWeight::get().writes(3_u64))
+	}
+	fn vested_transfer() -> Weight {
+		Weight::from_parts(42_871_000, 21801)
+			.saturating_add(RocksDbWeight::get().reads(27_u64))
+			.saturating_add(RocksDbWeight::get().writes(12_u64))
+	}
+	fn vest() -> Weight {
+		Weight::from_parts(18_015_000, 4634)
 			.saturating_add(RocksDbWeight::get().reads(1_u64))
 			.saturating_add(RocksDbWeight::get().writes(2_u64))
 	}
+	fn snapshot() -> Weight {
+		Weight::from_parts(7_285_000, 1489)
+			.saturating_add(RocksDbWeight::get().reads(1_u64))
+			.saturating_add(RocksDbWeight::get().writes(1_u64))
+	}
+	fn delegate() -> Weight {
+		Weight::from_parts(28_637_000, 6108)
+			.saturating_add(RocksDbWeight::get().reads(6_u64))
+			.saturating_add(RocksDbWeight::get().writes(5_u64))
+	}
+	fn flash_mint() -> Weight {
+		Weight::from_parts(73_418_000, 11755)
+			.saturating_add(RocksDbWeight::get().reads(31_u64))
+			.saturating_add(RocksDbWeight::get().writes(17_u64))
+	}
+	fn freeze_account() -> Weight {
+		Weight::from_parts(9_102_000, 0)
+			.saturating_add(RocksDbWeight::get().writes(1_u64))
+	}
+	fn thaw_account() -> Weight {
+		Weight::from_parts(8_973_000, 0)
+			.saturating_add(RocksDbWeight::get().writes(1_u64))
+	}
+	fn add_to_allowlist() -> Weight {
+		Weight::from_parts(9_215_000, 0)
+			.saturating_add(RocksDbWeight::get().writes(1_u64))
+	}
+	fn remove_from_allowlist() -> Weight {
+		Weight::from_parts(9_037_000, 0)
+			.saturating_add(RocksDbWeight::get().writes(1_u64))
+	}
+	/// The range of component `n` is `[1, 500]`.
+	fn batch_add_to_allowlist(n: u32, ) -> Weight {
+		Weight::from_parts(5_406_231, 0)
+			.saturating_add(Weight::from_parts(2_961_774, 0).saturating_mul(n.into()))
+			.saturating_add(RocksDbWeight::get().writes((1_u64).saturating_mul(n.into())))
+	}
+	/// The range of component `n` is `[1, 500]`.
+	fn batch_remove_from_allowlist(n: u32, ) -> Weight {
+		Weight::from_parts(5_218_904, 0)
+			.saturating_add(Weight::from_parts(2_904_319, 0).saturating_mul(n.into()))
+			.saturating_add(RocksDbWeight::get().writes((1_u64).saturating_mul(n.into())))
+	}
+	fn set_allowlist_enabled() -> Weight {
+		Weight::from_parts(6_712_000, 0)
+			.saturating_add(RocksDbWeight::get().writes(1_u64))
+	}
+	fn set_transfer_fee() -> Weight {
+		Weight::from_parts(6_874_000, 0)
+			.saturating_add(RocksDbWeight::get().writes(1_u64))
+	}
+	fn set_transfer_fee_exempt() -> Weight {
+		Weight::from_parts(9_146_000, 0)
+			.saturating_add(RocksDbWeight::get().writes(1_u64))
+	}
 }
\ No newline at end of file
@@ -6,17 +6,20 @@
 #[cfg(feature = "std")]
 include!(concat!(env!("OUT_DIR"), "/wasm_binary.rs"));
 
+use codec::{Decode, Encode};
 use pallet_grandpa::AuthorityId as GrandpaId;
+use scale_info::TypeInfo;
 use sp_api::impl_runtime_apis;
 use sp_consensus_aura::sr25519::AuthorityId as AuraId;
 use sp_core::{crypto::KeyTypeId, OpaqueMetadata};
 use sp_runtime::{
 	create_runtime_str, generic, impl_opaque_keys,
 	traits::{
-		AccountIdLookup, BlakeTwo256, Block as BlockT, IdentifyAccount, NumberFor, One, Verify,
+		AccountIdConversion, AccountIdLookup, BlakeTwo256, Block as BlockT, IdentifyAccount,
+		ConvertInto, NumberFor, One, Verify,
 	},
 	transaction_validity::{TransactionSource, TransactionValidity},
-	ApplyExtrinsicResult, MultiSignature,
+	ApplyExtrinsicResult, FixedU128, MultiSignature, RuntimeDebug,
 };
 use sp_std::prelude::*;
 #[cfg(feature = "std")]
@@ -27,8 +30,8 @@ use sp_version::RuntimeVersion;
 pub use frame_support::{
 	construct_runtime, parameter_types,
 	traits::{
-		ConstBool, ConstU128, ConstU32, ConstU64, ConstU8, KeyOwnerProofSystem, Randomness,
-		StorageInfo,
+		ConstBool, ConstU128, ConstU16, ConstU32, ConstU64, ConstU8, KeyOwnerProofSystem,
+		Randomness, StorageInfo,
 	},
 	weights::{
 		constants::{
@@ -38,10 +41,12 @@ pub use frame_support::{
 	},
 	StorageValue,
 };
+use frame_support::PalletId;
 pub use frame_system::Call as SystemCall;
+use frame_system::EnsureRoot;
 pub use pallet_balances::Call as BalancesCall;
 pub use pallet_timestamp::Call as TimestampCall;
-use pallet_transaction_payment::{ConstFeeMultiplier, CurrencyAdapter, Multiplier};
+use pallet_transaction_payment::{ConstFeeMultiplier, Multiplier};
 #[cfg(any(feature = "std", test))]
 pub use sp_runtime::BuildStorage;
 pub use sp_runtime::{Perbill, Permill};
@@ -104,10 +109,10 @@ pub const VERSION: RuntimeVersion = RuntimeVersion {
 	//   `spec_version`, and `authoring_version` are the same between Wasm and native.
 	// This value is set to 100 to notify Polkadot-JS App (https://polkadot.js.org/apps) to use
 	//   the compatible custom types.
-	spec_version: 100,
+	spec_version: 101,
 	impl_version: 1,
 	apis: RUNTIME_API_VERSIONS,
-	transaction_version: 1,
+	transaction_version: 2,
 	state_version: 1,
 };
 
@@ -255,7 +260,10 @@ parameter_types! {
 
 impl pallet_transaction_payment::Config for Runtime {
 	type RuntimeEvent = RuntimeEvent;
-	type OnChargeTransaction = CurrencyAdapter<Balances, ()>;
+	#[cfg(not(feature = "usd-token-fees"))]
+	type OnChargeTransaction = pallet_transaction_payment::CurrencyAdapter<Balances, ()>;
+	#[cfg(feature = "usd-token-fees")]
+	type OnChargeTransaction = erc20::payment::TokenFeeAdapter<Runtime, erc20::Instance1>;
 	type OperationalFeeMultiplier = ConstU8<5>;
 	type WeightToFee = IdentityFee<Balance>;
 	type LengthToFee = IdentityFee<Balance>;
@@ -268,10 +276,145 @@ impl pallet_sudo::Config for Runtime {
 	type WeightInfo = pallet_sudo::weights::SubstrateWeight<Runtime>;
 }
 
-impl erc20::Config for Runtime {
+/// Signs permits with a new sr25519 key in the ERC20 benchmarks.
+#[cfg(feature = "runtime-benchmarks")]
+pub struct PermitBenchmarkHelper;
+
+#[cfg(feature = "runtime-benchmarks")]
+impl erc20::BenchmarkHelper<sp_runtime::MultiSigner, AccountId, Signature>
+	for PermitBenchmarkHelper
+{
+	fn signer() -> (sp_runtime::MultiSigner, AccountId) {
+		let signer = sp_runtime::MultiSigner::from(sp_io::crypto::sr25519_generate(0.into(), None));
+		(signer.clone(), signer.into_account())
+	}
+
+	fn sign(signer: &sp_runtime::MultiSigner, message: &[u8]) -> Signature {
+		match signer {
+			sp_runtime::MultiSigner::Sr25519(public) => MultiSignature::Sr25519(
+				sp_io::crypto::sr25519_sign(0.into(), public, message)
+					.expect("the key was generated by `signer`"),
+			),
+			_ => unreachable!("`signer` only generates sr25519 keys"),
+		}
+	}
+}
+
+/// Supply cap of `UsdToken`: one billion tokens with 6 decimals.
+pub const USD_TOKEN_MAX_SUPPLY: Balance = 1_000_000_000 * 1_000_000;
+
+/// Supply cap of `GovToken`: one billion tokens with 18 decimals.
+pub const GOV_TOKEN_MAX_SUPPLY: Balance = 1_000_000_000 * 1_000_000_000_000_000_000;
+
+/// Native deposit reserved for each allowance of the ERC20 tokens.
+pub const ALLOWANCE_DEPOSIT: Balance = 100 * EXISTENTIAL_DEPOSIT;
+
+parameter_types! {
+	/// Receives the transaction fees paid in the ERC20 tokens.
+	pub TokenFeeCollector: AccountId = PalletId(*b"erc20fee").into_account_truncating();
+	/// Receives the transfer fees of the ERC20 tokens.
+	pub TokenTreasury: AccountId = PalletId(*b"erc20trs").into_account_truncating();
+	/// One USD (10^6 units) per native token (10^12 units).
+	pub UsdFeeRate: FixedU128 = FixedU128::from_rational(1, 1_000_000);
+	/// One GOV (10^18 units) per native token (10^12 units).
+	pub GovFeeRate: FixedU128 = FixedU128::from_u32(1_000_000);
+	/// Fee of the ERC20 flash mints.
+	pub FlashMintFee: Permill = Permill::from_parts(900);
+}
+
+/// Stablecoin-like token hosted by the first ERC20 pallet instance.
+impl erc20::Config<erc20::Instance1> for Runtime {
 	type RuntimeEvent = RuntimeEvent;
+	type RuntimeCall = RuntimeCall;
 	type WeightInfo = erc20::weights::SubstrateWeight<Runtime>;
+	type Balance = Balance;
+	// One hundredth of a cent.
+	type MinimumBalance = ConstU128<100>;
+	type MaxSupply = ConstU128<USD_TOKEN_MAX_SUPPLY>;
+	type Decimals = ConstU64<6>;
+	type StringLimit = ConstU32<50>;
+	type AdminOrigin = EnsureRoot<AccountId>;
+	type RoleAdminOrigin = EnsureRoot<AccountId>;
+	type MaxRoleMembers = ConstU32<100>;
+	type PauseOrigin = EnsureRoot<AccountId>;
+	type FreezeOrigin = EnsureRoot<AccountId>;
+	type MaxBatchSize = ConstU32<500>;
+	type MaxVestingSchedules = ConstU32<28>;
+	type BlockNumberToBalance = ConvertInto;
+	type MaxFlashMint = ConstU128<{ USD_TOKEN_MAX_SUPPLY / 10 }>;
+	type FlashMintFee = FlashMintFee;
+	type FlashMintFeeReceiver = TokenFeeCollector;
+	type MaxFeeBps = ConstU16<100>;
+	type TransferFeeAccount = TokenTreasury;
+	type FeeCollector = TokenFeeCollector;
+	type DefaultFeeRate = UsdFeeRate;
+	type Currency = Balances;
+	type AllowanceDeposit = ConstU128<ALLOWANCE_DEPOSIT>;
+	type RuntimeHoldReason = RuntimeHoldReason;
+	type OffchainSignature = Signature;
+	type OffchainPublic = <Signature as Verify>::Signer;
+	#[cfg(feature = "runtime-benchmarks")]
+	type BenchmarkHelper = PermitBenchmarkHelper;
+}
+
+/// Governance token hosted by the second ERC20 pallet instance.
+impl erc20::Config<erc20::Instance2> for Runtime {
+	type RuntimeEvent = RuntimeEvent;
+	type RuntimeCall = RuntimeCall;
+	type WeightInfo = erc20::weights::SubstrateWeight<Runtime>;
+	type Balance = Balance;
+	type MinimumBalance = ConstU128<1>;
+	type MaxSupply = ConstU128<GOV_TOKEN_MAX_SUPPLY>;
 	type Decimals = ConstU64<18>;
+	type StringLimit = ConstU32<50>;
+	type AdminOrigin = EnsureRoot<AccountId>;
+	type RoleAdminOrigin = EnsureRoot<AccountId>;
+	type MaxRoleMembers = ConstU32<100>;
+	type PauseOrigin = EnsureRoot<AccountId>;
+	type FreezeOrigin = EnsureRoot<AccountId>;
+	type MaxBatchSize = ConstU32<500>;
+	type MaxVestingSchedules = ConstU32<28>;
+	type BlockNumberToBalance = ConvertInto;
+	// Flash minted governance tokens could sway votes counted at the current block.
+	type MaxFlashMint = ConstU128<0>;
+	type FlashMintFee = FlashMintFee;
+	type FlashMintFeeReceiver = TokenFeeCollector;
+	type MaxFeeBps = ConstU16<100>;
+	type TransferFeeAccount = TokenTreasury;
+	type FeeCollector = TokenFeeCollector;
+	type DefaultFeeRate = GovFeeRate;
+	type Currency = Balances;
+	type AllowanceDeposit = ConstU128<ALLOWANCE_DEPOSIT>;
+	type RuntimeHoldReason = RuntimeHoldReason;
+	type OffchainSignature = Signature;
+	type OffchainPublic = <Signature as Verify>::Signer;
+	#[cfg(feature = "runtime-benchmarks")]
+	type BenchmarkHelper = PermitBenchmarkHelper;
+}
+
+/// Selects one of the ERC20 token instances in the ERC20 runtime APIs.
+#[derive(Clone, Copy, Encode, Decode, Eq, PartialEq, RuntimeDebug, TypeInfo)]
+pub enum TokenId {
+	/// The `UsdToken` instance.
+	Usd,
+	/// The `GovToken` instance.
+	Gov,
+}
+
+/// Runs `$body` with `$pallet` aliased to the ERC20 pallet instance selected by `$token`.
+macro_rules! with_token {
+	($token:expr, $pallet:ident => $body:expr) => {
+		match $token {
+			TokenId::Usd => {
+				type $pallet = UsdToken;
+				$body
+			},
+			TokenId::Gov => {
+				type $pallet = GovToken;
+				$body
+			},
+		}
+	};
 }
 
 // Create the runtime by composing the FRAME pallets that were previously configured.
@@ -285,7 +428,8 @@ construct_runtime!(
 		TransactionPayment: pallet_transaction_payment,
 		Sudo: pallet_sudo,
 		// Include the custom logic from the pallet-template in the runtime.
-		ERCModule: erc20,
+		UsdToken: erc20::<Instance1>,
+		GovToken: erc20::<Instance2>,
 	}
 );
 
@@ -312,6 +456,29 @@ pub type UncheckedExtrinsic =
 	generic::UncheckedExtrinsic<Address, RuntimeCall, Signature, SignedExtra>;
 /// The payload being signed in transactions.
 pub type SignedPayload = generic::SignedPayload<RuntimeCall, SignedExtra>;
+
+parameter_types! {
+	/// Name of the pallet before it was split into the `UsdToken` and `GovToken` instances.
+	pub const ErcModulePalletName: &'static str = "ERCModule";
+}
+
+/// Migrations to apply on runtime upgrade.
+pub type Migrations = (
+	erc20::migrations::v0::MovePalletPrefix<Runtime, erc20::Instance1, ErcModulePalletName>,
+	erc20::migrations::v1::RecomputeTotalSupply<Runtime, erc20::Instance1>,
+	erc20::migrations::v1::RecomputeTotalSupply<Runtime, erc20::Instance2>,
+	erc20::migrations::v2::InitMinterCount<Runtime, erc20::Instance1>,
+	erc20::migrations::v2::InitMinterCount<Runtime, erc20::Instance2>,
+	erc20::migrations::v3::MintersToRoles<Runtime, erc20::Instance1>,
+	erc20::migrations::v3::MintersToRoles<Runtime, erc20::Instance2>,
+	erc20::migrations::v4::ReapDustAccounts<Runtime, erc20::Instance1>,
+	erc20::migrations::v4::ReapDustAccounts<Runtime, erc20::Instance2>,
+	erc20::migrations::v5::ReserveAllowanceDeposits<Runtime, erc20::Instance1>,
+	erc20::migrations::v5::ReserveAllowanceDeposits<Runtime, erc20::Instance2>,
+	erc20::migrations::v6::CheckpointTotalSupply<Runtime, erc20::Instance1>,
+	erc20::migrations::v6::CheckpointTotalSupply<Runtime, erc20::Instance2>,
+);
+
 /// Executive: handles dispatch to the various modules.
 pub type Executive = frame_executive::Executive<
 	Runtime,
@@ -319,6 +486,7 @@ pub type Executive = frame_executive::Executive<
 	frame_system::ChainContext<Runtime>,
 	Runtime,
 	AllPalletsWithSystem,
+	Migrations,
 >;
 
 #[cfg(feature = "runtime-benchmarks")]
@@ -333,7 +501,8 @@ mod benches {
 		[pallet_balances, Balances]
 		[pallet_timestamp, Timestamp]
 		[pallet_sudo, Sudo]
-		[erc20, ERCModule]
+		[erc20, UsdToken]
+		[erc20, GovToken]
 	);
 }
 
@@ -505,6 +674,104 @@ impl_runtime_apis! {
 		}
 	}
 
+	impl erc20::runtime_api::AccessControlApi<Block, TokenId, AccountId> for Runtime {
+		fn has_role(token: TokenId, role: erc20::Role, account: AccountId) -> bool {
+			with_token!(token, Token => Token::has_role(role, &account))
+		}
+
+		fn role_members(token: TokenId, role: erc20::Role) -> Vec<AccountId> {
+			with_token!(token, Token => Token::role_members(role))
+		}
+	}
+
+	impl erc20::runtime_api::MetadataApi<Block, TokenId> for Runtime {
+		fn name(token: TokenId) -> Vec<u8> {
+			with_token!(token, Token => Token::name().into_inner())
+		}
+
+		fn symbol(token: TokenId) -> Vec<u8> {
+			with_token!(token, Token => Token::symbol().into_inner())
+		}
+
+		fn decimals(token: TokenId) -> u64 {
+			with_token!(token, Token => Token::decimals())
+		}
+	}
+
+	impl erc20::runtime_api::FreezeApi<Block, TokenId, AccountId> for Runtime {
+		fn is_frozen(token: TokenId, who: AccountId) -> bool {
+			with_token!(token, Token => Token::is_frozen(&who))
+		}
+	}
+
+	impl erc20::runtime_api::SupplyApi<Block, TokenId, Balance> for Runtime {
+		fn total_supply(token: TokenId) -> Balance {
+			with_token!(token, Token => Token::total_supply())
+		}
+
+		fn remaining_mintable(token: TokenId) -> Balance {
+			with_token!(token, Token => Token::remaining_mintable())
+		}
+	}
+
+	impl erc20::runtime_api::BalancesApi<Block, TokenId, AccountId, Balance> for Runtime {
+		fn free_balance(token: TokenId, who: AccountId) -> Balance {
+			with_token!(token, Token => Token::balance_of(who))
+		}
+
+		fn reserved_balance(token: TokenId, who: AccountId) -> Balance {
+			with_token!(token, Token => Token::reserved_balance(who))
+		}
+	}
+
+	impl erc20::runtime_api::SnapshotApi<Block, TokenId, AccountId, Balance> for Runtime {
+		fn current_snapshot_id(token: TokenId) -> erc20::SnapshotId {
+			with_token!(token, Token => Token::current_snapshot_id())
+		}
+
+		fn balance_of_at(token: TokenId, who: AccountId, id: erc20::SnapshotId) -> Option<Balance> {
+			with_token!(token, Token => Token::balance_of_at(&who, id).ok())
+		}
+
+		fn total_supply_at(token: TokenId, id: erc20::SnapshotId) -> Option<Balance> {
+			with_token!(token, Token => Token::total_supply_at(id).ok())
+		}
+	}
+
+	impl erc20::runtime_api::VotesApi<Block, TokenId, AccountId, Balance, BlockNumber> for Runtime {
+		fn delegates(token: TokenId, who: AccountId) -> Option<AccountId> {
+			with_token!(token, Token => Token::delegates(who))
+		}
+
+		fn get_votes(token: TokenId, who: AccountId) -> Balance {
+			with_token!(token, Token => Token::get_votes(&who))
+		}
+
+		fn get_past_votes(token: TokenId, who: AccountId, block: BlockNumber) -> Option<Balance> {
+			with_token!(token, Token => Token::get_past_votes(&who, block).ok())
+		}
+
+		fn get_past_total_supply(token: TokenId, block: BlockNumber) -> Option<Balance> {
+			with_token!(token, Token => Token::get_past_total_supply(block).ok())
+		}
+	}
+
+	impl erc20::runtime_api::PermitApi<Block, TokenId, AccountId, Balance, BlockNumber> for Runtime {
+		fn permit_nonce(token: TokenId, owner: AccountId) -> u64 {
+			with_token!(token, Token => Token::permit_nonce(owner))
+		}
+
+		fn permit_payload(
+			token: TokenId,
+			owner: AccountId,
+			spender: AccountId,
+			value: Balance,
+			deadline: BlockNumber,
+		) -> Vec<u8> {
+			with_token!(token, Token => Token::permit_payload(owner, spender, value, deadline).encode())
+		}
+	}
+
 	#[cfg(feature = "runtime-benchmarks")]
 	impl frame_benchmarking::Benchmark<Block> for Runtime {
 		fn benchmark_metadata(extra: bool) -> (